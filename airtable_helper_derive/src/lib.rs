@@ -0,0 +1,289 @@
+//! `#[derive(AirtableRecord)]` for `airtable_helper` table models.
+//!
+//! The derive implements `Serialize`, `Deserialize` and
+//! `airtable_helper::AirtableRecord` for a struct with named fields, and
+//! generates a `<Struct>Changes` builder for partial updates.
+//!
+//! Attributes:
+//! - `#[airtable(table = "Tasks")]` on the struct: table name or ID
+//!   (defaults to the struct name).
+//! - `#[airtable(field = "Name")]` on a field: column name or field ID
+//!   (defaults to the field name, without `r#`). Writable fields cannot be
+//!   named `is_empty` or `into_record`, which the changes builder uses.
+//! - `#[airtable(read_only)]`: computed column (formula, lookup...), read but never sent.
+//! - `#[airtable(default)]`: use `Default` when Airtable omits the column
+//!   (it does for empty cells and unchecked checkboxes). `Option` fields are
+//!   always `None` when omitted.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Type};
+
+#[proc_macro_derive(AirtableRecord, attributes(airtable))]
+pub fn derive_airtable_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// A struct field and its Airtable mapping
+struct ModelField {
+    ident: syn::Ident,
+    ty: Type,
+    column: String,
+    read_only: bool,
+    default: bool,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "AirtableRecord cannot be derived for generic structs",
+        ));
+    }
+
+    let named = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref named) => named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "AirtableRecord needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "AirtableRecord can only be derived for structs",
+            ))
+        }
+    };
+
+    let table = table_name(&input)?;
+    let fields = named
+        .named
+        .iter()
+        .map(model_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let changes_ident = format_ident!("{}Changes", ident);
+
+    // Setters share the changes builder with these methods
+    for field in fields.iter().filter(|f| !f.read_only) {
+        if field.ident == "is_empty" || field.ident == "into_record" {
+            return Err(Error::new_spanned(
+                &field.ident,
+                format!(
+                    "`{}` clashes with a method of `{}`; rename the field and map it with `#[airtable(field = \"...\")]`",
+                    field.ident, changes_ident
+                ),
+            ));
+        }
+    }
+
+    let field_idents: Vec<_> = fields.iter().map(|f| &f.ident).collect();
+    let field_types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+    let columns: Vec<_> = fields.iter().map(|f| f.column.as_str()).collect();
+    let read_only_columns: Vec<_> = fields
+        .iter()
+        .filter(|f| f.read_only)
+        .map(|f| f.column.as_str())
+        .collect();
+
+    // Serde attributes of the mirror structs
+    let ser_attrs: Vec<_> = fields
+        .iter()
+        .map(|f| {
+            let column = &f.column;
+            if f.read_only {
+                quote!(#[serde(rename = #column, skip_serializing)])
+            } else if is_option(&f.ty) {
+                quote!(#[serde(rename = #column, skip_serializing_if = "Option::is_none")])
+            } else {
+                quote!(#[serde(rename = #column)])
+            }
+        })
+        .collect();
+    let de_attrs: Vec<_> = fields
+        .iter()
+        .map(|f| {
+            let column = &f.column;
+            if f.default || is_option(&f.ty) {
+                quote!(#[serde(rename = #column, default)])
+            } else {
+                quote!(#[serde(rename = #column)])
+            }
+        })
+        .collect();
+
+    // One setter per writable field on the changes builder
+    let setters = fields.iter().filter(|f| !f.read_only).map(|f| {
+        let field_ident = &f.ident;
+        let ty = &f.ty;
+        let column = &f.column;
+        let doc = format!("Sets `{}`.", column);
+        quote! {
+            #[doc = #doc]
+            pub fn #field_ident(mut self, value: #ty) -> Self {
+                self.0.set(#column, &value);
+                self
+            }
+        }
+    });
+
+    let changes_doc = format!(
+        "Partial update of a `{}`: only the fields that are set get sent.",
+        ident
+    );
+
+    Ok(quote! {
+        const _: () = {
+            use ::airtable_helper::__private::serde;
+
+            // Read-only fields are skipped, so never read here
+            #[allow(dead_code)]
+            #[derive(serde::Serialize)]
+            #[serde(crate = "::airtable_helper::__private::serde")]
+            struct __Ser<'a> {
+                #( #ser_attrs #field_idents: &'a #field_types, )*
+            }
+
+            #[derive(serde::Deserialize)]
+            #[serde(crate = "::airtable_helper::__private::serde")]
+            struct __De {
+                #( #de_attrs #field_idents: #field_types, )*
+            }
+
+            impl serde::Serialize for #ident {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                    serde::Serialize::serialize(
+                        &__Ser { #( #field_idents: &self.#field_idents, )* },
+                        serializer,
+                    )
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for #ident {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+                    let __De { #( #field_idents, )* } = <__De as serde::Deserialize>::deserialize(deserializer)?;
+                    ::std::result::Result::Ok(#ident { #( #field_idents, )* })
+                }
+            }
+
+            impl ::airtable_helper::types::model::AirtableRecord for #ident {
+                type Changes = #changes_ident;
+                const TABLE: &'static str = #table;
+                const FIELDS: &'static [&'static str] = &[ #( #columns ),* ];
+                const READ_ONLY_FIELDS: &'static [&'static str] = &[ #( #read_only_columns ),* ];
+            }
+        };
+
+        #[doc = #changes_doc]
+        #[derive(Debug, Default)]
+        #vis struct #changes_ident(::airtable_helper::types::model::RecordChanges);
+
+        impl #changes_ident {
+            #( #setters )*
+
+            /// Whether no field was set.
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            /// Record to pass to `update_records` (in merge mode) for `record_id`.
+            pub fn into_record(
+                self,
+                record_id: &str,
+            ) -> ::std::result::Result<
+                ::airtable_helper::types::records::Record,
+                ::airtable_helper::client::AirtableError,
+            > {
+                self.0.into_record(record_id)
+            }
+        }
+
+        impl ::std::convert::From<::airtable_helper::types::model::RecordChanges> for #changes_ident {
+            fn from(changes: ::airtable_helper::types::model::RecordChanges) -> Self {
+                Self(changes)
+            }
+        }
+
+        impl ::std::convert::From<#changes_ident> for ::airtable_helper::types::model::RecordChanges {
+            fn from(changes: #changes_ident) -> Self {
+                changes.0
+            }
+        }
+    })
+}
+
+// `#[airtable(table = "...")]`, defaulting to the struct name
+fn table_name(input: &DeriveInput) -> syn::Result<String> {
+    let mut table = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("airtable")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                table = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("expected `table = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(table.unwrap_or_else(|| input.ident.to_string()))
+}
+
+// Reads the `#[airtable(...)]` attributes of a field
+fn model_field(field: &syn::Field) -> syn::Result<ModelField> {
+    let ident = field.ident.clone().expect("named field");
+    let mut column = None;
+    let mut read_only = false;
+    let mut default = false;
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("airtable")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("field") {
+                let value = meta.value()?.parse::<LitStr>()?;
+                if value.value().is_empty() {
+                    return Err(Error::new_spanned(value, "field must not be empty"));
+                }
+                column = Some(value.value());
+                Ok(())
+            } else if meta.path.is_ident("read_only") {
+                read_only = true;
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                default = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `field = \"...\"`, `read_only` or `default`"))
+            }
+        })?;
+    }
+
+    Ok(ModelField {
+        column: column.unwrap_or_else(|| ident.unraw().to_string()),
+        ident,
+        ty: field.ty.clone(),
+        read_only,
+        default,
+    })
+}
+
+// Whether the type is spelled `Option<...>`
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
@@ -0,0 +1,32 @@
+use std::env;
+use dotenv::dotenv;
+use airtable_helper::client::AirtableClient;
+use airtable_helper::client::AirtableError;
+
+#[tokio::main]
+async fn main() -> Result<(), AirtableError> {
+    dotenv().ok();
+
+    let api_key = env::var("AIRTABLE_API_KEY")
+        .expect("Expected AIRTABLE_API_KEY to be set");
+    let base_id = env::var("AIRTABLE_BASE_ID")
+        .expect("Expected AIRTABLE_BASE_ID to be set");
+
+    let client = AirtableClient::new(&api_key, &base_id);
+
+    // Bases the token can see
+    for base in client.list_bases().await? {
+        println!("{} ({}): {:?}", base.name, base.id, base.permission_level);
+    }
+
+    // Tables and fields of the client's base
+    let schema = client.get_base_schema().await?;
+    for table in &schema.tables {
+        println!("\n{} ({})", table.name, table.id);
+        for field in &table.fields {
+            println!("  {}: {}", field.name, field.config.type_name());
+        }
+    }
+
+    Ok(())
+}
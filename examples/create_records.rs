@@ -31,7 +31,7 @@ async fn main() -> Result<(), AirtableError> {
 
     let new_records = vec![rec1, rec2];
     let created = client
-        .create_records("Table 1", &new_records)
+        .create_records("Table 1", &new_records, None)
         .await?;
 
     for record in created {
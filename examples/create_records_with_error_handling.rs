@@ -2,6 +2,7 @@ use std::env;
 use dotenv::dotenv;
 use airtable_helper::{
     client::{AirtableClient, AirtableError},
+    types::params::CreateRecordsParams,
     types::records::Record,
 };
 use serde_json::json;
@@ -34,7 +35,13 @@ async fn main() -> Result<(), AirtableError> {
     let mut created_records: Option<Vec<Record>> = None;
     let mut remaining_records: Option<Vec<Record>> = None;
 
-    match client.create_records("Table 1", &new_records).await {
+    // Keep creating the valid records when some are rejected
+    let params = CreateRecordsParams {
+        isolate_failures: true,
+        ..Default::default()
+    };
+
+    match client.create_records("Table 1", &new_records, Some(params)).await {
         // 1) Full success
         Ok(created) => {
             println!("All records created successfully! Count: {}", created.len());
@@ -47,11 +54,17 @@ async fn main() -> Result<(), AirtableError> {
             processed_count,
             remaining,
             message,
+            failures,
         }) => {
             eprintln!("Partial success: {processed_count} records created so far.");
             eprintln!("Error message: {message}");
             eprintln!("{} records were NOT created yet.", remaining.len());
 
+            // Records Airtable rejected, with the reason
+            for failure in &failures {
+                eprintln!("Record #{} rejected: {}", failure.index, failure.error);
+            }
+
             // The chunk that succeeded
             created_records = Some(processed);
 
@@ -0,0 +1,36 @@
+use std::env;
+use dotenv::dotenv;
+use airtable_helper::client::{AirtableClient, AirtableError};
+
+#[tokio::main]
+async fn main() -> Result<(), AirtableError> {
+    dotenv().ok();
+
+    let api_key = env::var("AIRTABLE_API_KEY")
+        .expect("Expected AIRTABLE_API_KEY to be set");
+    let base_id = env::var("AIRTABLE_BASE_ID")
+        .expect("Expected AIRTABLE_BASE_ID to be set");
+
+    let client = AirtableClient::new(&api_key, &base_id);
+
+    let record_ids = vec![
+        "rec7FUJc4UNF5cimY".to_string(),
+        "rec2stbejBgiKOceX".to_string(),
+    ];
+
+    match client.delete_records("Table 1", &record_ids).await {
+        Ok(deleted) => println!("Deleted {} records: {:?}", deleted.len(), deleted),
+        Err(AirtableError::PartialDeleteError {
+            deleted,
+            remaining,
+            message,
+            ..
+        }) => {
+            eprintln!("Deleted {:?} before failing: {message}", deleted);
+            eprintln!("{} records were NOT deleted.", remaining.len());
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use std::env;
 use dotenv::dotenv;
 use airtable_helper::client::AirtableClient;
 use airtable_helper::client::AirtableError;
+use airtable_helper::types::records::Record;
 
 #[tokio::main]
 async fn main() -> Result<(), AirtableError> {
@@ -15,7 +16,7 @@ async fn main() -> Result<(), AirtableError> {
     let client = AirtableClient::new(&api_key, &base_id);
     let record_id = "rec46ly3TlQcVDkkO";
     let table_name = "Table 1";
-    let record = client.get_record(table_name, record_id).await?;
+    let record: Record = client.get_record(table_name, record_id, None).await?;
 
     println!("{}", record);
 
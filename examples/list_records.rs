@@ -1,5 +1,6 @@
 use std::env;
 use airtable_helper::types::params::ListRecordsParams;
+use airtable_helper::types::records::Record;
 use dotenv::dotenv;
 use airtable_helper::client::AirtableClient;
 use airtable_helper::client::AirtableError;
@@ -24,15 +25,15 @@ async fn main() -> Result<(), AirtableError> {
         view: Some("Grid view".to_string()),
         max_records: Some(3),
         fields: Some(vec!["Name".to_string(), "created".to_string()]),
-        sort: Some(("Name".to_string(), "asc".to_string())),
+        sort: Some(vec![Sort::asc("Name"), Sort::desc("created")]),
     }; */
     
-    let mut params = ListRecordsParams::new();
+    let params = ListRecordsParams::new();
     /* params.fields = Some(vec!["Name".to_string(), "created".to_string()]);
     params.max_records = Some(4); */
     let table_name = "Table 1";
 
-    let records = client
+    let records: Vec<Record> = client
         .list_records(table_name, Some(params))
         .await?;
 
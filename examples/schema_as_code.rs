@@ -0,0 +1,32 @@
+use std::env;
+use dotenv::dotenv;
+use airtable_helper::client::{AirtableClient, AirtableError};
+use airtable_helper::declarative::DeclaredSchema;
+
+// cargo run --features declarative --example schema_as_code -- examples/schema.toml [--apply]
+#[tokio::main]
+async fn main() -> Result<(), AirtableError> {
+    dotenv().ok();
+
+    let api_key = env::var("AIRTABLE_API_KEY")
+        .expect("Expected AIRTABLE_API_KEY to be set");
+    let base_id = env::var("AIRTABLE_BASE_ID")
+        .expect("Expected AIRTABLE_BASE_ID to be set");
+
+    let path = env::args().nth(1).unwrap_or_else(|| "examples/schema.toml".to_string());
+    let apply = env::args().any(|arg| arg == "--apply");
+
+    let client = AirtableClient::new(&api_key, &base_id);
+    let declared = DeclaredSchema::from_path(&path)?;
+
+    // Review the plan first, like any other infrastructure change
+    let plan = client.plan_schema(&declared).await?;
+    print!("{}", plan);
+
+    if apply && !plan.is_empty() {
+        let applied = client.apply_schema_plan(&plan).await?;
+        println!("Applied {} changes.", applied);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use std::env;
+use std::pin::pin;
+use dotenv::dotenv;
+use futures::TryStreamExt;
+use airtable_helper::client::{AirtableClient, AirtableError};
+
+#[tokio::main]
+async fn main() -> Result<(), AirtableError> {
+    dotenv().ok();
+
+    let api_key = env::var("AIRTABLE_API_KEY")
+        .expect("Expected AIRTABLE_API_KEY to be set");
+    let base_id = env::var("AIRTABLE_BASE_ID")
+        .expect("Expected AIRTABLE_BASE_ID to be set");
+
+    let client = AirtableClient::new(&api_key, &base_id);
+
+    // Records are processed as each page arrives, without holding the whole table
+    let mut records = pin!(client.stream_records::<serde_json::Value>("Table 1", None));
+    let mut count = 0;
+    while let Some(record) = records.try_next().await? {
+        println!("Record ID: {:?}", record.id);
+        count += 1;
+    }
+
+    println!("Streamed {} records", count);
+
+    Ok(())
+}
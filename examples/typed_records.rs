@@ -0,0 +1,46 @@
+use std::env;
+use dotenv::dotenv;
+use serde::{Deserialize, Serialize};
+use airtable_helper::{
+    client::{AirtableClient, AirtableError},
+    types::records::Record,
+};
+
+/// Fields of "Table 1", mapped from the Airtable column names
+#[derive(Debug, Serialize, Deserialize)]
+struct Task {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Status", default, skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), AirtableError> {
+    dotenv().ok();
+
+    let api_key = env::var("AIRTABLE_API_KEY")
+        .expect("Expected AIRTABLE_API_KEY to be set");
+    let base_id = env::var("AIRTABLE_BASE_ID")
+        .expect("Expected AIRTABLE_BASE_ID to be set");
+
+    let client = AirtableClient::new(&api_key, &base_id);
+
+    let tasks = client.list_records::<Task>("Table 1", None).await?;
+    for task in &tasks {
+        println!("{:?}: {} ({:?})", task.id, task.fields.name, task.fields.status);
+    }
+
+    let new_task = Record {
+        id: None,
+        fields: Task {
+            name: "Typed task".to_string(),
+            status: Some("Todo".to_string()),
+        },
+        created_time: None,
+    };
+    let created = client.create_records("Table 1", &[new_task], None).await?;
+    println!("Created {:?}", created[0].id);
+
+    Ok(())
+}
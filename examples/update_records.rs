@@ -3,6 +3,7 @@ use std::env;
 use dotenv::dotenv;
 use airtable_helper::{
     client::{AirtableClient, AirtableError},
+    types::params::{UpdateMode, UpdateRecordsParams},
     types::records::Record,
 };
 
@@ -33,12 +34,29 @@ async fn main() -> Result<(), AirtableError> {
 
     let new_records = vec![rec1, rec2];
     let created = client
-        .update_records("Table 1", &new_records)
+        .update_records("Table 1", &new_records, None)
         .await?;
 
     for record in created {
         println!("Updated record: id={:?}, fields={}", record.id, record.fields);
     }
 
+    // Replace mode (PUT): "Status" is cleared since it is not sent
+    let mut params = UpdateRecordsParams::new();
+    params.mode = UpdateMode::Replace;
+
+    let replacement = Record {
+        id: Some("rec7FUJc4UNF5cimY".to_string()),
+        fields: json!({ "Name": "Alice" }),
+        created_time: None,
+    };
+    let replaced = client
+        .update_records("Table 1", &[replacement], Some(params))
+        .await?;
+
+    for record in replaced {
+        println!("Replaced record: id={:?}, fields={}", record.id, record.fields);
+    }
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,234 @@
+use super::client::{AirtableClient, DEFAULT_BASE_URL};
+use super::error::AirtableError;
+use super::rate_limit::{RateLimit, RateLimiter};
+use super::retry::RetryPolicy;
+use std::time::Duration;
+
+/// User agent sent by clients the builder creates, unless overridden.
+pub const DEFAULT_USER_AGENT: &str = concat!("airtable_helper/", env!("CARGO_PKG_VERSION"));
+
+/// Builder for an `AirtableClient` with non-default settings.
+///
+/// Nothing is validated until `build()`, which reports every invalid
+/// setting as `AirtableError::Config`.
+pub struct AirtableClientBuilder {
+    api_key: String,
+    base_id: String,
+    base_url: Option<String>,
+    typecast: Option<bool>,
+    return_fields_by_field_id: bool,
+    retry_policy: RetryPolicy,
+    rate_limiting: RateLimiting,
+
+    // Settings for the internally created `reqwest::Client`
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<String>,
+
+    /// Pre-built client, used as-is instead of the settings above
+    http_client: Option<reqwest::Client>,
+}
+
+// Last rate limit setting of the builder
+enum RateLimiting {
+    Limit(RateLimit),
+    Limiter(RateLimiter),
+    Disabled,
+}
+
+impl AirtableClientBuilder {
+    /// Creates a builder for the given API key and base ID.
+    pub fn new(api_key: &str, base_id: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            base_id: base_id.to_string(),
+            base_url: None,
+            typecast: Some(true),
+            return_fields_by_field_id: false,
+            retry_policy: RetryPolicy::default(),
+            rate_limiting: RateLimiting::Limit(RateLimit::default()),
+            timeout: None,
+            connect_timeout: None,
+            user_agent: None,
+            proxy: None,
+            http_client: None,
+        }
+    }
+
+    /// Overrides the API root (defaults to `https://api.airtable.com/v0`).
+    /// Useful to target a local mock server or a proxy path.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Default `typecast` for create and update requests (defaults to `Some(true)`).
+    pub fn typecast(mut self, typecast: Option<bool>) -> Self {
+        self.typecast = typecast;
+        self
+    }
+
+    /// Returns record fields keyed by field ID (`fld...`) instead of name on
+    /// every list, get and write request, so renamed columns do not break code.
+    /// Fields can always be written by ID.
+    pub fn return_fields_by_field_id(mut self, enabled: bool) -> Self {
+        self.return_fields_by_field_id = enabled;
+        self
+    }
+
+    /// Sets how rate-limited (429) and failed (5xx) requests are retried.
+    /// Use `RetryPolicy::none()` to disable retries.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the per-base request budget (defaults to Airtable's 5 requests/second).
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limiting = RateLimiting::Limit(rate_limit);
+        self
+    }
+
+    /// Uses an existing limiter, sharing its budget with other clients.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiting = RateLimiting::Limiter(rate_limiter);
+        self
+    }
+
+    /// Disables client-side rate limiting.
+    pub fn no_rate_limit(mut self) -> Self {
+        self.rate_limiting = RateLimiting::Disabled;
+        self
+    }
+
+    /// Total timeout for each request attempt, from connecting to reading the body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for establishing a connection.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// `User-Agent` header (defaults to `airtable_helper/<version>`).
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Routes all requests through the proxy at `proxy_url`
+    /// (e.g. `http://proxy.internal:3128`).
+    pub fn proxy(mut self, proxy_url: &str) -> Self {
+        self.proxy = Some(proxy_url.to_string());
+        self
+    }
+
+    /// Uses a pre-built `reqwest::Client`. It cannot be combined with
+    /// `timeout`, `connect_timeout`, `user_agent` or `proxy`, which only
+    /// configure the client the builder creates itself.
+    pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Validates the settings and builds the client.
+    pub fn build(self) -> Result<AirtableClient, AirtableError> {
+        if self.api_key.trim().is_empty() {
+            return Err(AirtableError::Config("API key must not be empty".to_string()));
+        }
+        if self.base_id.trim().is_empty() {
+            return Err(AirtableError::Config("base ID must not be empty".to_string()));
+        }
+
+        let base_url = match self.base_url {
+            Some(ref url) => {
+                let parsed = reqwest::Url::parse(url)
+                    .map_err(|e| AirtableError::Config(format!("invalid base URL '{}': {}", url, e)))?;
+                if parsed.scheme() != "http" && parsed.scheme() != "https" {
+                    return Err(AirtableError::Config(format!(
+                        "base URL '{}' must use http or https",
+                        url
+                    )));
+                }
+                // Endpoints append `/{base_id}/...`, so drop any trailing slash
+                url.trim_end_matches('/').to_string()
+            }
+            None => DEFAULT_BASE_URL.to_string(),
+        };
+
+        if self.retry_policy.max_attempts == 0 {
+            return Err(AirtableError::Config(
+                "retry policy needs at least one attempt".to_string(),
+            ));
+        }
+
+        let rate_limiter = match self.rate_limiting {
+            RateLimiting::Limit(ref limit) => Some(RateLimiter::new(limit.clone())?),
+            RateLimiting::Limiter(ref limiter) => Some(limiter.clone()),
+            RateLimiting::Disabled => None,
+        };
+
+        let http_client = match self.http_client {
+            Some(ref http_client) => {
+                if self.timeout.is_some()
+                    || self.connect_timeout.is_some()
+                    || self.user_agent.is_some()
+                    || self.proxy.is_some()
+                {
+                    return Err(AirtableError::Config(
+                        "timeout, connect_timeout, user_agent and proxy cannot be combined with a custom http_client".to_string(),
+                    ));
+                }
+                http_client.clone()
+            }
+            None => self.build_http_client()?,
+        };
+
+        Ok(AirtableClient {
+            api_key: self.api_key,
+            base_id: self.base_id,
+            http_client,
+            typecast: self.typecast,
+            return_fields_by_field_id: self.return_fields_by_field_id,
+            base_url,
+            retry_policy: self.retry_policy,
+            rate_limiter,
+        })
+    }
+
+    // Creates the `reqwest::Client` from the timeout, user agent and proxy settings
+    fn build_http_client(&self) -> Result<reqwest::Client, AirtableError> {
+        let mut http_builder = reqwest::Client::builder()
+            .user_agent(self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT));
+
+        if let Some(timeout) = self.timeout {
+            if timeout.is_zero() {
+                return Err(AirtableError::Config("timeout must be greater than zero".to_string()));
+            }
+            http_builder = http_builder.timeout(timeout);
+        }
+
+        if let Some(connect_timeout) = self.connect_timeout {
+            if connect_timeout.is_zero() {
+                return Err(AirtableError::Config(
+                    "connect timeout must be greater than zero".to_string(),
+                ));
+            }
+            http_builder = http_builder.connect_timeout(connect_timeout);
+        }
+
+        if let Some(ref proxy_url) = self.proxy {
+            let proxy = reqwest::Proxy::all(proxy_url.as_str())
+                .map_err(|e| AirtableError::Config(format!("invalid proxy URL '{}': {}", proxy_url, e)))?;
+            http_builder = http_builder.proxy(proxy);
+        }
+
+        http_builder
+            .build()
+            .map_err(|e| AirtableError::Config(format!("could not create HTTP client: {}", e)))
+    }
+}
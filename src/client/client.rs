@@ -1,14 +1,49 @@
+use super::builder::AirtableClientBuilder;
 use super::error::AirtableError;
-use crate::endpoints::records::{create_records, get_record, list_records, update_records};
-use crate::types::params::ListRecordsParams;
-use crate::types::records::Record;
+use super::rate_limit::RateLimiter;
+use super::retry::RetryPolicy;
+#[cfg(feature = "declarative")]
+use crate::declarative::{DeclaredSchema, SchemaPlan};
+use crate::endpoints::bases::{create_base, get_base_schema, list_bases};
+use crate::endpoints::fields::{create_field, update_field};
+use crate::endpoints::tables::{create_table, update_table};
+use crate::endpoints::records::{
+    create_records, delete_records, get_record, list_records, list_records_page, stream_pages,
+    stream_records, update_records, upsert_records,
+};
+use crate::types::params::{
+    CreateRecordsParams, GetRecordParams, ListRecordsParams, UpdateFieldParams, UpdateRecordsParams, UpdateTableParams,
+    UpsertParams,
+};
+use crate::types::records::{Record, RecordList, UpsertResult};
+use crate::types::schema::{Base, BaseSchema, CreatedBase, Field, FieldDefinition, Table, TableDefinition};
+use futures::Stream;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Default root of the Airtable REST API, used unless the builder overrides it.
+pub const DEFAULT_BASE_URL: &str = "https://api.airtable.com/v0";
 
 /// The core Airtable client, responsible for making requests to the API.
+///
+/// Cloning is cheap and clones share the HTTP connection pool and rate limiter.
+/// Use `AirtableClient::builder` to change any setting; they cannot be
+/// modified once the client is built.
+#[derive(Clone)]
 pub struct AirtableClient {
-    pub api_key: String,
-    pub base_id: String,
-    pub http_client: reqwest::Client,
-    pub typecast:  Option<bool>
+    pub(crate) api_key: String,
+    pub(crate) base_id: String,
+    pub(crate) http_client: reqwest::Client,
+    pub(crate) typecast: Option<bool>,
+    /// Return record fields keyed by field ID instead of name.
+    pub(crate) return_fields_by_field_id: bool,
+    /// Root URL every endpoint is built from (no trailing slash).
+    pub(crate) base_url: String,
+    /// How failed requests (429, 5xx, connection errors) are retried.
+    pub(crate) retry_policy: RetryPolicy,
+    /// Per-base request budget awaited before every request (`None` disables it).
+    pub(crate) rate_limiter: Option<RateLimiter>,
 }
 
 impl AirtableClient {
@@ -18,67 +53,308 @@ impl AirtableClient {
             api_key: api_key.to_string(),
             base_id: base_id.to_string(),
             http_client: reqwest::Client::new(),
-            typecast: Some(true)
+            typecast: Some(true),
+            return_fields_by_field_id: false,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: Some(RateLimiter::default()),
+        }
+    }
+
+    /// Returns a builder for a client with non-default settings
+    /// (timeouts, proxy, base URL, retries, rate limit...).
+    pub fn builder(api_key: &str, base_id: &str) -> AirtableClientBuilder {
+        AirtableClientBuilder::new(api_key, base_id)
+    }
+
+    /// ID of the base this client works on.
+    pub fn base_id(&self) -> &str {
+        &self.base_id
+    }
+
+    /// Root URL of the API.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Default `typecast` for create and update requests.
+    pub fn typecast(&self) -> Option<bool> {
+        self.typecast
+    }
+
+    /// Whether records come back keyed by field ID (`fld...`) instead of name.
+    pub fn return_fields_by_field_id(&self) -> bool {
+        self.return_fields_by_field_id
+    }
+
+    /// The retry policy applied to every request.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// The rate limiter awaited before every request, if enabled.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// The underlying HTTP client.
+    pub fn http_client(&self) -> &reqwest::Client {
+        &self.http_client
+    }
+
+    /// URL of a table in the client's base: `{base_url}/{base_id}/{table_name}`
+    pub(crate) fn table_url(&self, table_name: &str) -> String {
+        format!("{}/{}/{}", self.base_url, self.base_id, table_name)
+    }
+
+    /// URL of a single record: `{base_url}/{base_id}/{table_name}/{record_id}`
+    pub(crate) fn record_url(&self, table_name: &str, record_id: &str) -> String {
+        format!("{}/{}", self.table_url(table_name), record_id)
+    }
+
+    /// URL of a Metadata API endpoint: `{base_url}/meta/{path}`
+    pub(crate) fn meta_url(&self, path: &str) -> String {
+        format!("{}/meta/{}", self.base_url, path)
+    }
+
+    /// Sends a request, retrying according to the client's `RetryPolicy`.
+    /// Every attempt first waits for the rate limiter, if any.
+    ///
+    /// Returns the last response even if it is not a success, so callers keep
+    /// handling errors with `handle_airtable_error`.
+    pub(crate) async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, AirtableError> {
+        self.send_with_retries(request, true).await
+    }
+
+    /// Like `send`, for requests that create something (POST writes).
+    /// A 5xx or a timeout may come after Airtable applied the request, so these
+    /// are only retried on 429 and connection errors, unless the policy opts in.
+    pub(crate) async fn send_non_idempotent(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, AirtableError> {
+        self.send_with_retries(request, self.retry_policy.retry_non_idempotent).await
+    }
+
+    async fn send_with_retries(
+        &self,
+        request: reqwest::RequestBuilder,
+        idempotent: bool,
+    ) -> Result<reqwest::Response, AirtableError> {
+        let policy = &self.retry_policy;
+        let mut attempt = 1;
+
+        loop {
+            let is_last = attempt >= policy.max_attempts;
+            let current = request
+                .try_clone()
+                .ok_or_else(|| AirtableError::Other("Failed to clone request".to_string()))?;
+
+            if let Some(ref limiter) = self.rate_limiter {
+                limiter.acquire(&self.base_id).await;
+            }
+
+            let delay = match current.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    // A 429 is rejected before anything is applied
+                    let retryable = if idempotent {
+                        RetryPolicy::is_retryable_status(status)
+                    } else {
+                        status == StatusCode::TOO_MANY_REQUESTS
+                    };
+                    if is_last || !retryable {
+                        return Ok(response);
+                    }
+                    policy.delay_for_response(attempt, status, response.headers())
+                }
+                Err(err) => {
+                    // A connection error means the request never reached Airtable
+                    let retryable = err.is_connect() || (idempotent && err.is_timeout());
+                    if is_last || !retryable {
+                        return Err(err.into());
+                    }
+                    policy.backoff(attempt)
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
         }
     }
 
-    pub async fn list_records(
+    /// Fetches every record of `table_name` (following offsets), with fields
+    /// deserialized into `T` (`serde_json::Value` with `list_records::<Value>`).
+    pub async fn list_records<T: DeserializeOwned>(
         &self,
         table_name: &str,
         params: Option<ListRecordsParams>,
-    ) -> Result<Vec<Record>, AirtableError> {
+    ) -> Result<Vec<Record<T>>, AirtableError> {
         let params = params.unwrap_or_default();
 
         list_records(self, table_name, &params).await
     }
 
-    pub async fn get_record(
+    /// Fetches one page of records, starting at `params.offset`.
+    /// Pass the returned `offset` back in the params to get the next page.
+    pub async fn list_records_page<T: DeserializeOwned>(
+        &self,
+        table_name: &str,
+        params: Option<ListRecordsParams>,
+    ) -> Result<RecordList<T>, AirtableError> {
+        let params = params.unwrap_or_default();
+
+        list_records_page(self, table_name, &params).await
+    }
+
+    /// Streams the records of `table_name` as pages arrive, instead of
+    /// collecting them all. Dropping the stream stops further requests.
+    pub fn stream_records<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        table_name: &str,
+        params: Option<ListRecordsParams>,
+    ) -> impl Stream<Item = Result<Record<T>, AirtableError>> + 'a {
+        let params = params.unwrap_or_default();
+
+        stream_records(self, table_name, &params)
+    }
+
+    /// Streams the pages of `table_name`, each with up to `page_size` records.
+    pub fn stream_pages<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        table_name: &str,
+        params: Option<ListRecordsParams>,
+    ) -> impl Stream<Item = Result<RecordList<T>, AirtableError>> + 'a {
+        let params = params.unwrap_or_default();
+
+        stream_pages(self, table_name, &params)
+    }
+
+    /// Fetches a single record, with fields deserialized into `T`.
+    pub async fn get_record<T: DeserializeOwned>(
         &self,
         table_name: &str,
         record_id: &str,
-    ) -> Result<Record, AirtableError> {
-        get_record(self, table_name, record_id).await
+        params: Option<GetRecordParams>,
+    ) -> Result<Record<T>, AirtableError> {
+        let params = params.unwrap_or_default();
+
+        get_record(self, table_name, record_id, &params).await
     }
 
-    pub async fn create_records(
+    /// Creates records. `typecast` defaults to the client setting unless
+    /// overridden in `params`.
+    pub async fn create_records<T: Serialize + DeserializeOwned>(
         &self,
         table_name: &str,
-        records: &[Record],
-    ) -> Result<Vec<Record>, AirtableError> {
-        create_records(self, table_name, records).await
+        records: &[Record<T>],
+        params: Option<CreateRecordsParams>,
+    ) -> Result<Vec<Record<T>>, AirtableError> {
+        let params = params.unwrap_or_default();
+
+        create_records(self, table_name, records, &params).await
     }
 
-    pub async fn update_records(
+    /// Updates records. By default fields are merged (PATCH); use
+    /// `UpdateMode::Replace` to clear every field that is not sent (PUT).
+    pub async fn update_records<T: Serialize + DeserializeOwned>(
         &self,
         table_name: &str,
-        records: &[Record],
-    ) -> Result<Vec<Record>, AirtableError> {
-        update_records(self, table_name, records).await
+        records: &[Record<T>],
+        params: Option<UpdateRecordsParams>,
+    ) -> Result<Vec<Record<T>>, AirtableError> {
+        let params = params.unwrap_or_default();
+
+        update_records(self, table_name, records, &params).await
     }
 
+    /// Creates or updates records, matching existing ones on `params.fields_to_merge_on`.
+    pub async fn upsert_records<T: Serialize + DeserializeOwned>(
+        &self,
+        table_name: &str,
+        records: &[Record<T>],
+        params: UpsertParams,
+    ) -> Result<UpsertResult<T>, AirtableError> {
+        upsert_records(self, table_name, records, &params).await
+    }
 
-    // No General upload function for now -> painful error handling
-/*     pub async fn upload_records(
+    /// Deletes the records with the given IDs, returning the deleted IDs.
+    pub async fn delete_records(
         &self,
         table_name: &str,
-        records: &[Record],
-    ) -> Result<Vec<Record>, AirtableError> {
-        let (new, existing): (Vec<_>, Vec<_>) =
-            records.iter().cloned().partition(|r| r.id.is_none());
-        let mut all = Vec::new();
-
-        if !new.is_empty() {
-            all.extend(create_records(self, table_name, &new).await?);
-        }
-        if !existing.is_empty() {
-            all.extend(update_records(self, table_name, &existing).await?);
-        }
+        record_ids: &[String],
+    ) -> Result<Vec<String>, AirtableError> {
+        delete_records(self, table_name, record_ids).await
+    }
+
+    /// Lists every base the token can access, with its permission level.
+    pub async fn list_bases(&self) -> Result<Vec<Base>, AirtableError> {
+        list_bases(self).await
+    }
 
-        Ok(all)
+    /// Fetches the tables, fields and views of the client's base.
+    pub async fn get_base_schema(&self) -> Result<BaseSchema, AirtableError> {
+        get_base_schema(self, &self.base_id).await
     }
 
-    pub async fn placeholder(&self) -> Result<(), AirtableError> {
-        // logic to call airtable endpoints
-        Ok(())
-    } */
+    /// Creates a base in `workspace_id` with the given tables.
+    /// Use a client for the new base ID to work with it afterwards.
+    pub async fn create_base(
+        &self,
+        workspace_id: &str,
+        name: &str,
+        tables: &[TableDefinition],
+    ) -> Result<CreatedBase, AirtableError> {
+        create_base(self, workspace_id, name, tables).await
+    }
+
+    /// Creates a table in the client's base; the first field of `table` is the primary field.
+    pub async fn create_table(&self, table: &TableDefinition) -> Result<Table, AirtableError> {
+        create_table(self, table).await
+    }
+
+    /// Changes the name and/or description of a table.
+    pub async fn update_table(
+        &self,
+        table_id_or_name: &str,
+        params: UpdateTableParams,
+    ) -> Result<Table, AirtableError> {
+        update_table(self, table_id_or_name, &params).await
+    }
+
+    /// Adds a field to `table_id`. Computed fields (formulas...) cannot be created.
+    pub async fn create_field(
+        &self,
+        table_id: &str,
+        field: &FieldDefinition,
+    ) -> Result<Field, AirtableError> {
+        create_field(self, table_id, field).await
+    }
+
+    /// Changes the name and/or description of a field.
+    pub async fn update_field(
+        &self,
+        table_id: &str,
+        field_id: &str,
+        params: UpdateFieldParams,
+    ) -> Result<Field, AirtableError> {
+        update_field(self, table_id, field_id, &params).await
+    }
+
+    /// Compares `declared` with the live schema of the client's base.
+    /// Print the plan to review it, then pass it to `apply_schema_plan`.
+    #[cfg(feature = "declarative")]
+    pub async fn plan_schema(&self, declared: &DeclaredSchema) -> Result<SchemaPlan, AirtableError> {
+        let live = self.get_base_schema().await?;
+        declared.plan(&live)
+    }
+
+    /// Makes the supported changes of `plan`, returning how many were made.
+    #[cfg(feature = "declarative")]
+    pub async fn apply_schema_plan(&self, plan: &SchemaPlan) -> Result<usize, AirtableError> {
+        plan.apply(self).await
+    }
 }
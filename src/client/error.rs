@@ -1,6 +1,8 @@
-use reqwest::Response;
+use reqwest::{Response, StatusCode};
 use serde_json::Value;
+use std::fmt;
 use thiserror::Error;
+use crate::client::RetryPolicy;
 use crate::types::records::Record;
 
 // General error handling
@@ -12,6 +14,40 @@ pub enum AirtableError {
     #[error("JSON parsing error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("Invalid client configuration: {0}")]
+    Config(String),
+
+    #[error(
+        "Could not convert fields of record {}{}: {source}",
+        record_id.as_deref().unwrap_or("<new>"),
+        field.as_ref().map(|f| format!(" (field `{}`)", f)).unwrap_or_default()
+    )]
+    RecordConversion {
+        /// ID of the record, `None` for records not created yet
+        record_id: Option<String>,
+        /// Path of the offending field, when serde could tell
+        field: Option<String>,
+        source: serde_json::Error,
+    },
+
+    #[error("Invalid request parameters: {0}")]
+    InvalidParams(String),
+
+    #[error("Invalid schema definition: {0}")]
+    Schema(String),
+
+    #[error("{operation} failed ({status}): {kind} - {message}")]
+    Api {
+        /// HTTP status of the response
+        status: StatusCode,
+        /// Airtable's `error.type`
+        kind: ApiErrorKind,
+        /// Airtable's `error.message`, or the raw body when it is not JSON
+        message: String,
+        /// Operation that failed, e.g. "Create records"
+        operation: String,
+    },
+
     #[error("Other error occurred: {0}")]
     Other(String),
 
@@ -23,44 +59,269 @@ pub enum AirtableError {
         remaining: Vec<Record>,
         processed_count: usize,
         message: String,
-    }   
+        /// Records Airtable rejected, with the reason (only with `isolate_failures`)
+        failures: Vec<RecordFailure>,
+    },
+
+    /// Upsert that failed after some chunks were written
+    #[error("Partial upsert: created {} and updated {} records, then failed.\nError: {source}", created_records.len(), updated_records.len())]
+    PartialUpsertError {
+        /// IDs of the records created before the error
+        created_records: Vec<String>,
+        /// IDs of the existing records updated before the error
+        updated_records: Vec<String>,
+        /// The `PartialSuccessError` with the processed and remaining records
+        #[source]
+        source: Box<AirtableError>,
+    },
+
+    /// Never wraps `OffsetExpired`, which is returned as is since the listing cannot resume.
+    #[error("Partial list: fetched {} records, then failed.\nError: {source}", records.len())]
+    PartialListError {
+        /// Records fetched before the error
+        records: Vec<Record>,
+        /// Offset of the page that failed; set it as `ListRecordsParams::offset` to resume
+        offset: Option<String>,
+        #[source]
+        source: Box<AirtableError>,
+    },
+
+    #[error("List offset expired, listing must restart from the beginning: {0}")]
+    OffsetExpired(String),
+
+    #[error("Partial delete: deleted {deleted_count} records, then failed.\nError: {message}")]
+    PartialDeleteError {
+        /// IDs of the records deleted before the error
+        deleted: Vec<String>,
+        /// IDs that were not deleted yet (including the chunk that failed)
+        remaining: Vec<String>,
+        deleted_count: usize,
+        message: String,
+    },
+}
+
+/// A record rejected by Airtable, isolated from the rest of its chunk
+#[derive(Debug)]
+pub struct RecordFailure {
+    /// Position of the record in the slice passed to the write call
+    pub index: usize,
+    pub record: Record,
+    pub error: AirtableError,
+}
+
+impl AirtableError {
+    /// HTTP status of an API error.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            AirtableError::Api { status, .. } => Some(*status),
+            AirtableError::Http(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    /// Airtable error type of an API error.
+    pub fn kind(&self) -> Option<&ApiErrorKind> {
+        match self {
+            AirtableError::Api { kind, .. } => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// Whether sending the same request again may succeed: rate limits,
+    /// server errors, timeouts and connection errors.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            AirtableError::Api { status, .. } => RetryPolicy::is_retryable_status(*status),
+            AirtableError::Http(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Whether the base, table, view or record does not exist (or is not
+    /// visible with this token, which Airtable does not tell apart).
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            AirtableError::Api { status, kind, .. } => {
+                *status == StatusCode::NOT_FOUND || kind.is_not_found()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Airtable's `error.type`, see <https://airtable.com/developers/web/api/errors>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    AuthenticationRequired,
+    InvalidPermissions,
+    InvalidPermissionsOrModelNotFound,
+    NotFound,
+    ModelIdNotFound,
+    TableNotFound,
+    ViewNameNotFound,
+    RowDoesNotExist,
+    InvalidRequestUnknown,
+    InvalidRequestMissingFields,
+    InvalidRecords,
+    InvalidValueForColumn,
+    UnknownFieldName,
+    InvalidMultipleChoiceOptions,
+    InvalidFilterByFormula,
+    CannotUpdateComputedField,
+    ListRecordsIteratorNotAvailable,
+    RequestTooLarge,
+    RateLimitReached,
+    BillingLimitExceeded,
+    ServerError,
+    ServiceUnavailable,
+    /// Any other type, kept as sent
+    Unknown(String),
+}
+
+impl ApiErrorKind {
+    /// The type string Airtable uses.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ApiErrorKind::AuthenticationRequired => "AUTHENTICATION_REQUIRED",
+            ApiErrorKind::InvalidPermissions => "INVALID_PERMISSIONS",
+            ApiErrorKind::InvalidPermissionsOrModelNotFound => "INVALID_PERMISSIONS_OR_MODEL_NOT_FOUND",
+            ApiErrorKind::NotFound => "NOT_FOUND",
+            ApiErrorKind::ModelIdNotFound => "MODEL_ID_NOT_FOUND",
+            ApiErrorKind::TableNotFound => "TABLE_NOT_FOUND",
+            ApiErrorKind::ViewNameNotFound => "VIEW_NAME_NOT_FOUND",
+            ApiErrorKind::RowDoesNotExist => "ROW_DOES_NOT_EXIST",
+            ApiErrorKind::InvalidRequestUnknown => "INVALID_REQUEST_UNKNOWN",
+            ApiErrorKind::InvalidRequestMissingFields => "INVALID_REQUEST_MISSING_FIELDS",
+            ApiErrorKind::InvalidRecords => "INVALID_RECORDS",
+            ApiErrorKind::InvalidValueForColumn => "INVALID_VALUE_FOR_COLUMN",
+            ApiErrorKind::UnknownFieldName => "UNKNOWN_FIELD_NAME",
+            ApiErrorKind::InvalidMultipleChoiceOptions => "INVALID_MULTIPLE_CHOICE_OPTIONS",
+            ApiErrorKind::InvalidFilterByFormula => "INVALID_FILTER_BY_FORMULA",
+            ApiErrorKind::CannotUpdateComputedField => "CANNOT_UPDATE_COMPUTED_FIELD",
+            ApiErrorKind::ListRecordsIteratorNotAvailable => "LIST_RECORDS_ITERATOR_NOT_AVAILABLE",
+            ApiErrorKind::RequestTooLarge => "REQUEST_TOO_LARGE",
+            ApiErrorKind::RateLimitReached => "RATE_LIMIT_REACHED",
+            ApiErrorKind::BillingLimitExceeded => "PUBLIC_API_BILLING_LIMIT_EXCEEDED",
+            ApiErrorKind::ServerError => "SERVER_ERROR",
+            ApiErrorKind::ServiceUnavailable => "SERVICE_UNAVAILABLE",
+            ApiErrorKind::Unknown(kind) => kind,
+        }
+    }
+
+    /// Parses Airtable's type string, falling back to `Unknown`.
+    pub fn parse(kind: &str) -> Self {
+        match kind {
+            "AUTHENTICATION_REQUIRED" => ApiErrorKind::AuthenticationRequired,
+            "INVALID_PERMISSIONS" => ApiErrorKind::InvalidPermissions,
+            "INVALID_PERMISSIONS_OR_MODEL_NOT_FOUND" => ApiErrorKind::InvalidPermissionsOrModelNotFound,
+            "NOT_FOUND" => ApiErrorKind::NotFound,
+            "MODEL_ID_NOT_FOUND" => ApiErrorKind::ModelIdNotFound,
+            "TABLE_NOT_FOUND" => ApiErrorKind::TableNotFound,
+            "VIEW_NAME_NOT_FOUND" => ApiErrorKind::ViewNameNotFound,
+            "ROW_DOES_NOT_EXIST" => ApiErrorKind::RowDoesNotExist,
+            "INVALID_REQUEST_UNKNOWN" => ApiErrorKind::InvalidRequestUnknown,
+            "INVALID_REQUEST_MISSING_FIELDS" => ApiErrorKind::InvalidRequestMissingFields,
+            "INVALID_RECORDS" => ApiErrorKind::InvalidRecords,
+            "INVALID_VALUE_FOR_COLUMN" => ApiErrorKind::InvalidValueForColumn,
+            "UNKNOWN_FIELD_NAME" => ApiErrorKind::UnknownFieldName,
+            "INVALID_MULTIPLE_CHOICE_OPTIONS" => ApiErrorKind::InvalidMultipleChoiceOptions,
+            "INVALID_FILTER_BY_FORMULA" => ApiErrorKind::InvalidFilterByFormula,
+            "CANNOT_UPDATE_COMPUTED_FIELD" => ApiErrorKind::CannotUpdateComputedField,
+            "LIST_RECORDS_ITERATOR_NOT_AVAILABLE" => ApiErrorKind::ListRecordsIteratorNotAvailable,
+            "REQUEST_TOO_LARGE" => ApiErrorKind::RequestTooLarge,
+            "RATE_LIMIT_REACHED" => ApiErrorKind::RateLimitReached,
+            "PUBLIC_API_BILLING_LIMIT_EXCEEDED" => ApiErrorKind::BillingLimitExceeded,
+            "SERVER_ERROR" => ApiErrorKind::ServerError,
+            "SERVICE_UNAVAILABLE" => ApiErrorKind::ServiceUnavailable,
+            other => ApiErrorKind::Unknown(other.to_string()),
+        }
+    }
+
+    // Kind implied by the status when the body has no error type
+    fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => ApiErrorKind::AuthenticationRequired,
+            StatusCode::FORBIDDEN => ApiErrorKind::InvalidPermissions,
+            StatusCode::NOT_FOUND => ApiErrorKind::NotFound,
+            StatusCode::PAYLOAD_TOO_LARGE => ApiErrorKind::RequestTooLarge,
+            StatusCode::UNPROCESSABLE_ENTITY => ApiErrorKind::InvalidRequestUnknown,
+            StatusCode::TOO_MANY_REQUESTS => ApiErrorKind::RateLimitReached,
+            StatusCode::SERVICE_UNAVAILABLE => ApiErrorKind::ServiceUnavailable,
+            s if s.is_server_error() => ApiErrorKind::ServerError,
+            s => ApiErrorKind::Unknown(s.as_u16().to_string()),
+        }
+    }
+
+    /// Whether the kind means a missing base, table, view, field or record.
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            ApiErrorKind::NotFound
+                | ApiErrorKind::ModelIdNotFound
+                | ApiErrorKind::TableNotFound
+                | ApiErrorKind::ViewNameNotFound
+                | ApiErrorKind::RowDoesNotExist
+                | ApiErrorKind::InvalidPermissionsOrModelNotFound
+        )
+    }
+}
+
+impl fmt::Display for ApiErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 pub async fn handle_airtable_error(response: Response, operation_desc: &str) -> AirtableError {
     let status = response.status();
+    let api_error = |kind: ApiErrorKind, message: String| AirtableError::Api {
+        status,
+        kind,
+        message,
+        operation: operation_desc.to_string(),
+    };
+
     let text_body = match response.text().await {
         Ok(s) => s,
         Err(e) => {
-            return AirtableError::Other(format!(
-                "{} failed ({}), also could not read body: {}",
-                operation_desc, status, e
-            ))
+            return api_error(
+                ApiErrorKind::from_status(status),
+                format!("could not read body: {}", e),
+            )
         }
     };
 
     // Parse as json to get error info
     if let Ok(json_val) = serde_json::from_str::<Value>(&text_body) {
-        // Extract type and message from error object if available
-        if let Some(error_obj) = json_val.get("error") {
-            let err_type = error_obj
-                .get("type")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown error type");
-            let message = error_obj
-                .get("message")
-                .and_then(|v| v.as_str())
-                .unwrap_or("No error message provided");
-
-            return AirtableError::Other(format!(
-                "{} failed ({}): {} - {}",
-                operation_desc, status, err_type, message
-            ));
+        match json_val.get("error") {
+            // Extract type and message from error object if available
+            Some(Value::Object(error_obj)) => {
+                let kind = error_obj
+                    .get("type")
+                    .and_then(|v| v.as_str())
+                    .map(ApiErrorKind::parse)
+                    .unwrap_or_else(|| ApiErrorKind::from_status(status));
+                let message = error_obj
+                    .get("message")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("No error message provided")
+                    .to_string();
+
+                // Airtable no longer knows the offset the caller paged from
+                if kind == ApiErrorKind::ListRecordsIteratorNotAvailable {
+                    return AirtableError::OffsetExpired(format!("{} failed: {}", operation_desc, message));
+                }
+
+                return api_error(kind, message);
+            }
+            // Some errors (e.g. unknown base) only carry the type: {"error": "NOT_FOUND"}
+            Some(Value::String(err_type)) => {
+                return api_error(ApiErrorKind::parse(err_type), "No error message provided".to_string());
+            }
+            _ => {}
         }
     }
 
-    // Return raw text in case parsing failed
-    AirtableError::Other(format!(
-        "{} failed ({}): {}",
-        operation_desc, status, text_body
-    ))
+    // Keep the raw text in case parsing failed
+    api_error(ApiErrorKind::from_status(status), text_body)
 }
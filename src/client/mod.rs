@@ -1,5 +1,12 @@
+#[allow(clippy::module_inception)]
 pub mod client;
+pub mod builder;
 pub mod error;
+pub mod rate_limit;
+pub mod retry;
 
-pub use client::AirtableClient;
-pub use error::AirtableError;
\ No newline at end of file
+pub use builder::{AirtableClientBuilder, DEFAULT_USER_AGENT};
+pub use client::{AirtableClient, DEFAULT_BASE_URL};
+pub use error::{AirtableError, ApiErrorKind, RecordFailure};
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use retry::RetryPolicy;
\ No newline at end of file
@@ -0,0 +1,105 @@
+use super::error::AirtableError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Request budget for a single base.
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    /// Sustained number of requests per second.
+    pub requests_per_second: f64,
+    /// Number of requests that can be sent back to back after an idle period.
+    pub burst: u32,
+}
+
+// Airtable allows 5 requests per second per base.
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 5.0,
+            burst: 5,
+        }
+    }
+}
+
+/// Token bucket per base ID.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Client-side token-bucket rate limiter, keyed by base ID.
+///
+/// Clones share the same buckets, so every clone of an `AirtableClient`
+/// (and any client built with the same limiter) draws from one budget per base.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `limit` for every base.
+    ///
+    /// Fails with `AirtableError::Config` unless `requests_per_second` is
+    /// positive and finite and `burst` is at least 1.
+    pub fn new(limit: RateLimit) -> Result<Self, AirtableError> {
+        if !(limit.requests_per_second.is_finite() && limit.requests_per_second > 0.0) {
+            return Err(AirtableError::Config(
+                "rate limit must allow a positive number of requests per second".to_string(),
+            ));
+        }
+        if limit.burst == 0 {
+            return Err(AirtableError::Config(
+                "rate limit burst must be at least 1".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            limit,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// The budget applied to each base.
+    pub fn limit(&self) -> &RateLimit {
+        &self.limit
+    }
+
+    /// Waits until a request to `base_id` fits in the budget, then consumes it.
+    pub async fn acquire(&self, base_id: &str) {
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+                let burst = self.limit.burst as f64;
+                let now = Instant::now();
+                let bucket = buckets.entry(base_id.to_string()).or_insert(Bucket {
+                    tokens: burst,
+                    last_refill: now,
+                });
+
+                // Refill for the time elapsed since the last request
+                let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+                bucket.tokens = (bucket.tokens + elapsed * self.limit.requests_per_second).min(burst);
+                bucket.last_refill = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.limit.requests_per_second)
+            };
+
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimit::default()).expect("default rate limit is valid")
+    }
+}
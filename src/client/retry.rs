@@ -0,0 +1,103 @@
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// Retry policy applied to every request sent by the client.
+///
+/// Requests answered with 429 (rate limited) or a 5xx status, as well as
+/// connection errors and timeouts, are retried with exponential backoff,
+/// except 429s without `Retry-After`, which wait `rate_limited_delay`.
+/// Creations (records, tables, fields, bases) are only retried on 429 and
+/// connection errors, unless `retry_non_idempotent` is set.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on every following retry.
+    pub base_delay: Duration,
+    /// Upper bound for a single delay, including `Retry-After` and
+    /// `rate_limited_delay`.
+    pub max_delay: Duration,
+    /// Delay after a 429 without `Retry-After`. Airtable does not send the
+    /// header and rejects every request for 30 seconds after a 429, so
+    /// retrying sooner only runs out of attempts.
+    pub rate_limited_delay: Duration,
+    /// Randomize delays (between half and the full backoff) so that
+    /// concurrent tasks do not retry in lockstep.
+    pub jitter: bool,
+    /// Wait for the duration given by a `Retry-After` header when present,
+    /// instead of the computed backoff.
+    pub respect_retry_after: bool,
+    /// Also retry creations on 5xx and timeouts. Airtable may have applied
+    /// the request before failing, so a retry can create duplicates.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            rate_limited_delay: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a response with this status should be retried.
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+    }
+
+    /// Backoff before retry number `retry` (starting at 1), capped at `max_delay`.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+
+        if self.jitter && !delay.is_zero() {
+            let half = delay / 2;
+            half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+        } else {
+            delay
+        }
+    }
+
+    /// Delay before retrying a response, preferring its `Retry-After` header,
+    /// then `rate_limited_delay` for a 429 (both capped at `max_delay`).
+    pub(crate) fn delay_for_response(&self, retry: u32, status: StatusCode, headers: &HeaderMap) -> Duration {
+        if self.respect_retry_after {
+            if let Some(delay) = retry_after(headers) {
+                return delay.min(self.max_delay);
+            }
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return self.rate_limited_delay.min(self.max_delay);
+        }
+        self.backoff(retry)
+    }
+}
+
+// `Retry-After` in seconds (Airtable does not send the HTTP-date form)
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
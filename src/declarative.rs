@@ -0,0 +1,464 @@
+//! Schema as code: describe the tables and fields of a base in a JSON, TOML
+//! or YAML file, compare it with the live schema and apply the difference.
+//!
+//! ```toml
+//! [[tables]]
+//! name = "Tasks"
+//! description = "Things to do"
+//!
+//! [[tables.fields]]
+//! name = "Name"
+//! type = "singleLineText"
+//!
+//! [[tables.fields]]
+//! name = "Status"
+//! type = "singleSelect"
+//! options = { choices = [{ name = "Todo", color = "blueLight2" }, { name = "Done" }] }
+//! ```
+//!
+//! Tables and fields are matched by name, ignoring case; a name that only
+//! differs in case is renamed to the declared one. Tables and fields of the
+//! base that the file does not mention are left alone. Changes the Metadata
+//! API cannot make (type or option changes, computed fields) are reported in
+//! `SchemaPlan::unsupported` and never attempted.
+//!
+//! Needs the `declarative` feature.
+
+use crate::client::{AirtableClient, AirtableError};
+use crate::types::params::{UpdateFieldParams, UpdateTableParams};
+use crate::types::schema::{BaseSchema, Field, FieldConfig, FieldDefinition, Table, TableDefinition};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+
+/// Desired tables of a base, as written in a schema file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeclaredSchema {
+    pub tables: Vec<TableDefinition>,
+}
+
+impl DeclaredSchema {
+    pub fn from_json(text: &str) -> Result<Self, AirtableError> {
+        serde_json::from_str(text).map_err(|e| AirtableError::Schema(format!("invalid JSON: {}", e)))
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, AirtableError> {
+        toml::from_str(text).map_err(|e| AirtableError::Schema(format!("invalid TOML: {}", e)))
+    }
+
+    pub fn from_yaml(text: &str) -> Result<Self, AirtableError> {
+        serde_yaml_ng::from_str(text).map_err(|e| AirtableError::Schema(format!("invalid YAML: {}", e)))
+    }
+
+    /// Reads a schema file, picking the format from its extension
+    /// (`.json`, `.toml`, `.yaml` or `.yml`).
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, AirtableError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            AirtableError::Schema(format!("could not read {}: {}", path.display(), e))
+        })?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&text),
+            Some("toml") => Self::from_toml(&text),
+            Some("yaml") | Some("yml") => Self::from_yaml(&text),
+            _ => Err(AirtableError::Schema(format!(
+                "{}: unknown schema format, expected .json, .toml, .yaml or .yml",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Checks that table names, and field names within a table, are set and
+    /// unique ignoring case. Otherwise two entries would plan conflicting
+    /// changes that only fail halfway through `apply`.
+    pub fn validate(&self) -> Result<(), AirtableError> {
+        let mut tables = HashSet::new();
+        for table in &self.tables {
+            if table.name.trim().is_empty() {
+                return Err(AirtableError::Schema("table name must not be empty".to_string()));
+            }
+            if !tables.insert(table.name.to_lowercase()) {
+                return Err(AirtableError::Schema(format!("two tables are named '{}'", table.name)));
+            }
+
+            let mut fields = HashSet::new();
+            for field in &table.fields {
+                if field.name.trim().is_empty() {
+                    return Err(AirtableError::Schema(format!(
+                        "table '{}' has a field without a name",
+                        table.name
+                    )));
+                }
+                if !fields.insert(field.name.to_lowercase()) {
+                    return Err(AirtableError::Schema(format!(
+                        "table '{}' has two fields named '{}'",
+                        table.name, field.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Changes needed to bring `live` to this schema, after `validate`.
+    pub fn plan(&self, live: &BaseSchema) -> Result<SchemaPlan, AirtableError> {
+        self.validate()?;
+        let mut plan = SchemaPlan::default();
+
+        for declared in &self.tables {
+            match live.tables.iter().find(|t| same_name(&t.name, &declared.name)) {
+                Some(table) => plan_table_changes(&mut plan, declared, table),
+                None => plan_new_table(&mut plan, declared),
+            }
+        }
+
+        Ok(plan)
+    }
+}
+
+/// A change the API can make
+#[derive(Debug, Clone)]
+pub enum SchemaChange {
+    CreateTable(TableDefinition),
+    UpdateTable {
+        table_id: String,
+        table_name: String,
+        /// Live description, shown next to the new one
+        old_description: Option<String>,
+        /// New name (only differing in case) and/or description
+        params: UpdateTableParams,
+    },
+    CreateField {
+        table_id: String,
+        table_name: String,
+        field: FieldDefinition,
+    },
+    UpdateField {
+        table_id: String,
+        table_name: String,
+        field_id: String,
+        field_name: String,
+        /// Live description, shown next to the new one
+        old_description: Option<String>,
+        /// New name (only differing in case) and/or description
+        params: UpdateFieldParams,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::CreateTable(table) => {
+                let fields: Vec<&str> = table.fields.iter().map(|f| f.name.as_str()).collect();
+                write!(f, "+ table {} (fields: {})", table.name, fields.join(", "))
+            }
+            SchemaChange::UpdateTable { table_name, old_description, params, .. } => {
+                write!(f, "~ table {}: ", table_name)?;
+                write_update(f, table_name, &params.name, old_description, &params.description)
+            }
+            SchemaChange::CreateField { table_name, field, .. } => {
+                write!(f, "+ field {}.{} ({})", table_name, field.name, field.config.type_name())
+            }
+            SchemaChange::UpdateField { table_name, field_name, old_description, params, .. } => {
+                write!(f, "~ field {}.{}: ", table_name, field_name)?;
+                write_update(f, field_name, &params.name, old_description, &params.description)
+            }
+        }
+    }
+}
+
+// `name "old" -> "new", description "old" -> "new"`, leaving out what does not
+// change; values are quoted so that line breaks stay on one line
+fn write_update(
+    f: &mut fmt::Formatter<'_>,
+    old_name: &str,
+    new_name: &Option<String>,
+    old_description: &Option<String>,
+    new_description: &Option<String>,
+) -> fmt::Result {
+    let quoted = |value: Option<&str>| match value {
+        Some(text) => format!("{:?}", text),
+        None => "(none)".to_string(),
+    };
+    let mut parts = Vec::new();
+    if let Some(name) = new_name {
+        parts.push(format!("name {} -> {}", quoted(Some(old_name)), quoted(Some(name))));
+    }
+    if new_description.is_some() {
+        parts.push(format!(
+            "description {} -> {}",
+            quoted(old_description.as_deref()),
+            quoted(new_description.as_deref())
+        ));
+    }
+    write!(f, "{}", parts.join(", "))
+}
+
+/// A difference the API cannot apply
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsupportedChange {
+    pub table: String,
+    /// `None` when the change is about the table itself
+    pub field: Option<String>,
+    pub reason: String,
+}
+
+impl fmt::Display for UnsupportedChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.field {
+            Some(ref field) => write!(f, "! field {}.{}: {}", self.table, field, self.reason),
+            None => write!(f, "! table {}: {}", self.table, self.reason),
+        }
+    }
+}
+
+/// Result of `DeclaredSchema::plan`; printing it lists every change
+#[derive(Debug, Clone, Default)]
+pub struct SchemaPlan {
+    /// Changes `apply` makes, in order
+    pub changes: Vec<SchemaChange>,
+    /// Differences that need a manual change in Airtable
+    pub unsupported: Vec<UnsupportedChange>,
+}
+
+impl SchemaPlan {
+    /// Whether the live schema already matches (apart from unsupported changes).
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Makes the supported changes in order, returning how many were made.
+    ///
+    /// Stops at the first error; the changes made before it are kept, so
+    /// planning again lists only what is left.
+    pub async fn apply(&self, client: &AirtableClient) -> Result<usize, AirtableError> {
+        for change in &self.changes {
+            match change {
+                SchemaChange::CreateTable(table) => {
+                    client.create_table(table).await?;
+                }
+                SchemaChange::UpdateTable { table_id, params, .. } => {
+                    client.update_table(table_id, params.clone()).await?;
+                }
+                SchemaChange::CreateField { table_id, field, .. } => {
+                    client.create_field(table_id, field).await?;
+                }
+                SchemaChange::UpdateField { table_id, field_id, params, .. } => {
+                    client.update_field(table_id, field_id, params.clone()).await?;
+                }
+            }
+        }
+
+        Ok(self.changes.len())
+    }
+}
+
+impl fmt::Display for SchemaPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() && self.unsupported.is_empty() {
+            return writeln!(f, "No changes, the base matches the schema.");
+        }
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        for unsupported in &self.unsupported {
+            writeln!(f, "{}", unsupported)?;
+        }
+        Ok(())
+    }
+}
+
+// New table: created with its creatable fields, computed ones are reported
+fn plan_new_table(plan: &mut SchemaPlan, declared: &TableDefinition) {
+    let mut table = declared.clone();
+    table.fields.retain(|field| {
+        if field.config.is_computed() {
+            plan.unsupported.push(computed_field(&declared.name, field));
+        }
+        !field.config.is_computed()
+    });
+
+    if table.fields.is_empty() {
+        plan.unsupported.push(UnsupportedChange {
+            table: declared.name.clone(),
+            field: None,
+            reason: "a new table needs at least one field that is not computed".to_string(),
+        });
+        return;
+    }
+
+    // The primary field must be the first one declared
+    if declared.fields.first().map(|f| &f.name) != table.fields.first().map(|f| &f.name) {
+        plan.unsupported.push(UnsupportedChange {
+            table: declared.name.clone(),
+            field: None,
+            reason: "the primary field (first field) cannot be computed".to_string(),
+        });
+        return;
+    }
+
+    plan.changes.push(SchemaChange::CreateTable(table));
+}
+
+// Existing table: name case, description and fields
+fn plan_table_changes(plan: &mut SchemaPlan, declared: &TableDefinition, table: &Table) {
+    let rename = declared.name != table.name;
+    let describe = declared.description.is_some() && declared.description != table.description;
+    if rename || describe {
+        plan.changes.push(SchemaChange::UpdateTable {
+            table_id: table.id.clone(),
+            table_name: table.name.clone(),
+            old_description: table.description.clone(),
+            params: UpdateTableParams {
+                name: rename.then(|| declared.name.clone()),
+                description: if describe { declared.description.clone() } else { None },
+            },
+        });
+    }
+
+    // The primary field is chosen when the table is created
+    if let (Some(first), Some(primary)) = (declared.fields.first(), table.primary_field()) {
+        if !same_name(&first.name, &primary.name) {
+            plan.unsupported.push(UnsupportedChange {
+                table: table.name.clone(),
+                field: Some(first.name.clone()),
+                reason: format!(
+                    "primary field is '{}', it cannot be changed through the API",
+                    primary.name
+                ),
+            });
+        }
+    }
+
+    for field in &declared.fields {
+        match table.fields.iter().find(|f| same_name(&f.name, &field.name)) {
+            Some(live) => plan_field_changes(plan, table, field, live),
+            None if field.config.is_computed() => {
+                plan.unsupported.push(computed_field(&table.name, field));
+            }
+            None => plan.changes.push(SchemaChange::CreateField {
+                table_id: table.id.clone(),
+                table_name: table.name.clone(),
+                field: field.clone(),
+            }),
+        }
+    }
+}
+
+// Existing field: only the name case and the description can be changed
+fn plan_field_changes(plan: &mut SchemaPlan, table: &Table, declared: &FieldDefinition, live: &Field) {
+    let rename = declared.name != live.name;
+    let describe = declared.description.is_some() && declared.description != live.description;
+    if rename || describe {
+        plan.changes.push(SchemaChange::UpdateField {
+            table_id: table.id.clone(),
+            table_name: table.name.clone(),
+            field_id: live.id.clone(),
+            field_name: live.name.clone(),
+            old_description: live.description.clone(),
+            params: UpdateFieldParams {
+                name: rename.then(|| declared.name.clone()),
+                description: if describe { declared.description.clone() } else { None },
+            },
+        });
+    }
+
+    let unsupported = |reason: String| UnsupportedChange {
+        table: table.name.clone(),
+        field: Some(live.name.clone()),
+        reason,
+    };
+
+    if declared.config.type_name() != live.config.type_name() {
+        plan.unsupported.push(unsupported(format!(
+            "type change {} -> {} is not supported",
+            live.config.type_name(),
+            declared.config.type_name()
+        )));
+        return;
+    }
+
+    match (&declared.config, &live.config) {
+        (FieldConfig::SingleSelect(want), FieldConfig::SingleSelect(have))
+        | (FieldConfig::MultipleSelects(want), FieldConfig::MultipleSelects(have)) => {
+            let added: Vec<&str> = want
+                .choices
+                .iter()
+                .filter(|c| !have.choices.iter().any(|h| h.name == c.name))
+                .map(|c| c.name.as_str())
+                .collect();
+            let removed: Vec<&str> = have
+                .choices
+                .iter()
+                .filter(|h| !want.choices.iter().any(|c| c.name == h.name))
+                .map(|h| h.name.as_str())
+                .collect();
+            let recolored = want.choices.iter().any(|c| {
+                c.color.is_some()
+                    && have
+                        .choices
+                        .iter()
+                        .any(|h| h.name == c.name && h.color != c.color)
+            });
+
+            if !added.is_empty() || !removed.is_empty() || recolored {
+                plan.unsupported.push(unsupported(format!(
+                    "select choices differ (add: [{}], remove: [{}]{}), choices cannot be changed through the API",
+                    added.join(", "),
+                    removed.join(", "),
+                    if recolored { ", colors changed" } else { "" }
+                )));
+            }
+        }
+        (want, have) => {
+            if !is_subset(&options_json(want), &options_json(have)) {
+                plan.unsupported.push(unsupported(
+                    "options differ, field options cannot be changed through the API".to_string(),
+                ));
+            }
+        }
+    }
+}
+
+// Airtable does not allow two tables (or two fields of a table) whose names
+// only differ in case, so names are compared that way
+fn same_name(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+fn computed_field(table: &str, field: &FieldDefinition) -> UnsupportedChange {
+    UnsupportedChange {
+        table: table.to_string(),
+        field: Some(field.name.clone()),
+        reason: format!(
+            "{} fields are computed and cannot be created through the API",
+            field.config.type_name()
+        ),
+    }
+}
+
+// `options` of a field config as JSON
+fn options_json(config: &FieldConfig) -> Value {
+    serde_json::to_value(config)
+        .ok()
+        .and_then(|mut v| v.get_mut("options").map(Value::take))
+        .unwrap_or(Value::Null)
+}
+
+// Whether every value set in `declared` has the same value in `live`;
+// Airtable fills in IDs and defaults the file does not need to repeat
+fn is_subset(declared: &Value, live: &Value) -> bool {
+    match (declared, live) {
+        (Value::Null, _) => true,
+        (Value::Object(want), Value::Object(have)) => want
+            .iter()
+            .all(|(key, value)| is_subset(value, have.get(key).unwrap_or(&Value::Null))),
+        (Value::Array(want), Value::Array(have)) => {
+            want.len() == have.len() && want.iter().zip(have).all(|(w, h)| is_subset(w, h))
+        }
+        (want, have) => want == have,
+    }
+}
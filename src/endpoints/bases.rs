@@ -0,0 +1,113 @@
+use crate::{
+    client::error::handle_airtable_error,
+    client::{AirtableClient, AirtableError},
+    types::schema::{Base, BaseList, BaseSchema, CreatedBase, TableDefinition},
+};
+use serde_json::json;
+use std::collections::HashSet;
+
+/// Lists every base the token can access (following offsets).
+pub async fn list_bases(client: &AirtableClient) -> Result<Vec<Base>, AirtableError> {
+    let url = client.meta_url("bases");
+    let mut bases = Vec::new();
+    let mut offset: Option<String> = None;
+
+    loop {
+        let mut request = client
+            .http_client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", client.api_key));
+        if let Some(ref off) = offset {
+            request = request.query(&[("offset", off)]);
+        }
+        let response = client.send(request).await?;
+
+        if !response.status().is_success() {
+            let err = handle_airtable_error(response, "List bases").await;
+            return Err(err);
+        }
+
+        let page: BaseList = response.json().await?;
+        bases.extend(page.bases);
+
+        match page.offset {
+            Some(next) => offset = Some(next),
+            None => break,
+        }
+    }
+
+    Ok(bases)
+}
+
+/// Fetches the tables (with fields and views) of `base_id`.
+pub async fn get_base_schema(
+    client: &AirtableClient,
+    base_id: &str,
+) -> Result<BaseSchema, AirtableError> {
+    let url = client.meta_url(&format!("bases/{}/tables", base_id));
+
+    let request = client
+        .http_client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", client.api_key));
+    let response = client.send(request).await?;
+
+    if !response.status().is_success() {
+        let err = handle_airtable_error(response, "Get base schema").await;
+        return Err(err);
+    }
+
+    let schema: BaseSchema = response.json().await?;
+
+    Ok(schema)
+}
+
+/// Creates a base named `name` in `workspace_id`, with `tables` in order.
+/// Returns the new base ID and its schema.
+pub async fn create_base(
+    client: &AirtableClient,
+    workspace_id: &str,
+    name: &str,
+    tables: &[TableDefinition],
+) -> Result<CreatedBase, AirtableError> {
+    if workspace_id.trim().is_empty() || name.trim().is_empty() {
+        return Err(AirtableError::InvalidParams(
+            "a base needs a workspace ID and a name".to_string(),
+        ));
+    }
+    if tables.is_empty() {
+        return Err(AirtableError::InvalidParams(format!(
+            "base '{}' needs at least one table",
+            name
+        )));
+    }
+    let mut names = HashSet::new();
+    for table in tables {
+        table.validate()?;
+        if !names.insert(table.name.to_lowercase()) {
+            return Err(AirtableError::InvalidParams(format!(
+                "base '{}' has two tables named '{}'",
+                name, table.name
+            )));
+        }
+    }
+
+    let url = client.meta_url("bases");
+    let body = json!({ "name": name, "workspaceId": workspace_id, "tables": tables });
+
+    let request = client
+        .http_client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", client.api_key))
+        .json(&body);
+    let response = client.send_non_idempotent(request).await?;
+
+    if !response.status().is_success() {
+        let err = handle_airtable_error(response, "Create base").await;
+        return Err(err);
+    }
+
+    let created: CreatedBase = response.json().await?;
+
+    Ok(created)
+}
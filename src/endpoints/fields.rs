@@ -0,0 +1,64 @@
+use crate::{
+    client::error::handle_airtable_error,
+    client::{AirtableClient, AirtableError},
+    types::params::UpdateFieldParams,
+    types::schema::{Field, FieldDefinition},
+};
+
+/// Adds a field to a table of the client's base.
+/// Computed fields (formulas, rollups...) are rejected before sending.
+pub async fn create_field(
+    client: &AirtableClient,
+    table_id: &str,
+    field: &FieldDefinition,
+) -> Result<Field, AirtableError> {
+    field.validate()?;
+    let url = client.meta_url(&format!("bases/{}/tables/{}/fields", client.base_id, table_id));
+
+    let request = client
+        .http_client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", client.api_key))
+        .json(field);
+    let response = client.send_non_idempotent(request).await?;
+
+    if !response.status().is_success() {
+        let err = handle_airtable_error(response, "Create field").await;
+        return Err(err);
+    }
+
+    let created: Field = response.json().await?;
+
+    Ok(created)
+}
+
+/// Renames a field and/or changes its description.
+/// The API does not allow changing the type or options of an existing field.
+pub async fn update_field(
+    client: &AirtableClient,
+    table_id: &str,
+    field_id: &str,
+    params: &UpdateFieldParams,
+) -> Result<Field, AirtableError> {
+    params.validate()?;
+    let url = client.meta_url(&format!(
+        "bases/{}/tables/{}/fields/{}",
+        client.base_id, table_id, field_id
+    ));
+
+    let request = client
+        .http_client
+        .patch(&url)
+        .header("Authorization", format!("Bearer {}", client.api_key))
+        .json(params);
+    let response = client.send(request).await?;
+
+    if !response.status().is_success() {
+        let err = handle_airtable_error(response, "Update field").await;
+        return Err(err);
+    }
+
+    let updated: Field = response.json().await?;
+
+    Ok(updated)
+}
@@ -1 +1,4 @@
-pub mod records;
\ No newline at end of file
+pub mod records;
+pub mod bases;
+pub mod tables;
+pub mod fields;
\ No newline at end of file
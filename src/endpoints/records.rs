@@ -1,108 +1,346 @@
 use crate::{
     client::error::handle_airtable_error,
-    client::{AirtableClient, AirtableError},
-    types::params::ListRecordsParams,
-    types::records::{Record, RecordList},
+    client::{AirtableClient, AirtableError, ApiErrorKind, RecordFailure},
+    types::params::{CreateRecordsParams, GetRecordParams, ListRecordsParams, UpdateRecordsParams, UpsertParams},
+    types::records::{
+        deserialize_records, serialize_records, DeletedRecord, Record, RecordList, UpsertResult,
+    },
 };
 
-use serde_json::{json, Value};
-
-// Fetches all records from a  `table_name` with the given params
-pub async fn list_records(
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::VecDeque;
+use std::pin::pin;
+
+// Fetches all records from a  `table_name` with the given params,
+// starting at `params.offset` if set.
+// If a page fails after others succeeded, returns a `PartialListError`
+// with the records fetched so far and the offset to resume from, except
+// for `OffsetExpired`: the cursor cannot be reused, so listing must restart.
+pub async fn list_records<T: DeserializeOwned>(
     client: &AirtableClient,
     table_name: &str,
     params: &ListRecordsParams,
-) -> Result<Vec<Record>, AirtableError> {
+) -> Result<Vec<Record<T>>, AirtableError> {
     let mut all_records = Vec::new();
-    let mut offset: Option<String> = None;
+    let mut offset = params.offset.clone();
+    let mut fetched_pages = 0;
 
-    // Build base request
-    let base_url = format!(
-        "https://api.airtable.com/v0/{base_id}/{table_name}",
-        base_id = client.base_id,
-        table_name = table_name
-    );
+    // in case of offset, pages are fetched until there is none left
+    let mut pages = pin!(stream_raw_pages(client, table_name, params));
+    loop {
+        match pages.try_next().await {
+            Ok(Some(record_list)) => {
+                fetched_pages += 1;
+                all_records.extend(record_list.records);
+                offset = record_list.offset;
+            }
+            Ok(None) => break,
+            Err(err) if fetched_pages == 0 => return Err(err),
+            Err(err @ AirtableError::OffsetExpired(_)) => return Err(err),
+            Err(err) => {
+                return Err(AirtableError::PartialListError {
+                    records: all_records,
+                    offset,
+                    source: Box::new(err),
+                })
+            }
+        }
+    }
 
-    let mut base_request = client
-        .http_client
-        .get(&base_url)
-        .header("Authorization", format!("Bearer {}", client.api_key));
+    deserialize_records(all_records)
+}
+
+/// Fetches a single page starting at `params.offset`. The returned
+/// `RecordList::offset` is the cursor of the next page, if any.
+pub async fn list_records_page<T: DeserializeOwned>(
+    client: &AirtableClient,
+    table_name: &str,
+    params: &ListRecordsParams,
+) -> Result<RecordList<T>, AirtableError> {
+    let request = ListRequest::new(client, table_name, params)?;
+
+    fetch_page(client, &request, params.offset.as_deref())
+        .await?
+        .deserialize_fields()
+}
+
+/// Lazily fetches the pages of `table_name`, one request per page, starting
+/// at `params.offset` if set.
+/// Nothing is sent until the stream is polled, and dropping it stops paging.
+pub fn stream_pages<'a, T: DeserializeOwned + 'a>(
+    client: &'a AirtableClient,
+    table_name: &str,
+    params: &ListRecordsParams,
+) -> impl Stream<Item = Result<RecordList<T>, AirtableError>> + 'a {
+    stream_raw_pages(client, table_name, params)
+        .map(|page| page.and_then(RecordList::deserialize_fields))
+}
+
+// Pages with JSON fields, see `stream_pages`
+fn stream_raw_pages<'a>(
+    client: &'a AirtableClient,
+    table_name: &str,
+    params: &ListRecordsParams,
+) -> impl Stream<Item = Result<RecordList, AirtableError>> + 'a {
+    let list_request = ListRequest::new(client, table_name, params);
+    let start = params.offset.clone();
+
+    stream::once(async move { list_request })
+        .map_ok(move |list_request| {
+            // State is the offset of the next page, `None` once the last page was fetched
+            stream::try_unfold(Some(start.clone()), move |next: Option<Option<String>>| {
+                let list_request = list_request.clone();
+                async move {
+                    let Some(offset) = next else {
+                        return Ok(None);
+                    };
+
+                    let record_list = fetch_page(client, &list_request, offset.as_deref()).await?;
+                    let next = record_list.offset.clone().map(Some);
+                    Ok(Some((record_list, next)))
+                }
+            })
+        })
+        .try_flatten()
+}
+
+/// Lazily fetches the records of `table_name`, page by page.
+pub fn stream_records<'a, T: DeserializeOwned + 'a>(
+    client: &'a AirtableClient,
+    table_name: &str,
+    params: &ListRecordsParams,
+) -> impl Stream<Item = Result<Record<T>, AirtableError>> + 'a {
+    stream_pages(client, table_name, params)
+        .map_ok(|record_list| stream::iter(record_list.records.into_iter().map(Ok)))
+        .try_flatten()
+}
+
+// Airtable rejects URLs longer than 16k characters; keep room for the offset param
+const MAX_LIST_URL_LENGTH: usize = 16_000 - 100;
+
+// A list request (without offset), sent either as a GET with the params in
+// the query string, or as a POST to `.../listRecords` with a JSON body.
+#[derive(Clone)]
+enum ListRequest {
+    Get {
+        url: String,
+        query: Vec<(String, String)>,
+    },
+    Post {
+        url: String,
+        body: Map<String, Value>,
+    },
+}
+
+impl ListRequest {
+    // Uses POST if asked to, or if the GET URL would be too long
+    fn new(
+        client: &AirtableClient,
+        table_name: &str,
+        params: &ListRecordsParams,
+    ) -> Result<Self, AirtableError> {
+        params.validate()?;
+
+        let url = client.table_url(table_name);
+        let by_field_id = params
+            .return_fields_by_field_id
+            .unwrap_or(client.return_fields_by_field_id);
+
+        let query = list_query(params, by_field_id);
+        if !params.force_post && encoded_url_length(&url, &query) <= MAX_LIST_URL_LENGTH {
+            return Ok(ListRequest::Get { url, query });
+        }
+
+        Ok(ListRequest::Post {
+            url: format!("{}/listRecords", url),
+            body: list_body(params, by_field_id),
+        })
+    }
+
+    // Request for the page at `offset`
+    fn request(&self, client: &AirtableClient, offset: Option<&str>) -> RequestBuilder {
+        let request = match self {
+            ListRequest::Get { url, query } => {
+                let mut request = client.http_client.get(url).query(query);
+                if let Some(off) = offset {
+                    request = request.query(&[("offset", off)]);
+                }
+                request
+            }
+            ListRequest::Post { url, body } => {
+                let mut body = body.clone();
+                if let Some(off) = offset {
+                    body.insert("offset".to_string(), json!(off));
+                }
+                client.http_client.post(url).json(&body)
+            }
+        };
+
+        request.header("Authorization", format!("Bearer {}", client.api_key))
+    }
+}
+
+// Query string params of a GET list request
+fn list_query(params: &ListRecordsParams, by_field_id: bool) -> Vec<(String, String)> {
+    let mut query: Vec<(String, String)> = Vec::new();
 
     // If `max_records` is Some, add `maxRecords=<value>`
     if let Some(mr) = params.max_records {
-        base_request = base_request.query(&[("maxRecords", mr.to_string())]);
+        query.push(("maxRecords".to_string(), mr.to_string()));
     }
 
     // If `view` is Some, add `view=<value>`
     if let Some(ref v) = params.view {
-        base_request = base_request.query(&[("view", v)]);
+        query.push(("view".to_string(), v.clone()));
     }
 
     // If `fields` is Some, add `fields[]=fieldName` for each field
     if let Some(ref fields_vec) = params.fields {
         for field_name in fields_vec {
-            base_request = base_request.query(&[("fields[]", field_name)]);
+            query.push(("fields[]".to_string(), field_name.clone()));
         }
     }
 
-    // If `sort` is Some, add `sort[0][field]` and `sort[0][direction]`
-    if let Some((ref sort_field, ref sort_direction)) = params.sort {
-        base_request = base_request
-            .query(&[("sort[0][field]", sort_field)])
-            .query(&[("sort[0][direction]", sort_direction)]);
+    // If `sort` is Some, add `sort[i][field]` and `sort[i][direction]` for each spec
+    if let Some(ref sorts) = params.sort {
+        for (i, sort) in sorts.iter().enumerate() {
+            query.push((format!("sort[{}][field]", i), sort.field.clone()));
+            query.push((format!("sort[{}][direction]", i), sort.direction.as_str().to_string()));
+        }
     }
 
-    // in case of offset
-    loop {
-        let mut request = base_request
-            .try_clone()
-            .ok_or_else(|| AirtableError::Other("Failed to clone request".to_string()))?;
-        if let Some(ref off) = offset {
-            request = request.query(&[("offset", off)]);
-        }
+    // If `filter_by_formula` is Some, add `filterByFormula=<formula>` (encoded by reqwest)
+    if let Some(ref formula) = params.filter_by_formula {
+        query.push(("filterByFormula".to_string(), formula.clone()));
+    }
 
-        // Get request
-        let response = request.send().await?;
+    // If `page_size` is Some, add `pageSize=<value>`
+    if let Some(ps) = params.page_size {
+        query.push(("pageSize".to_string(), ps.to_string()));
+    }
 
-        // Return Error in case of non success code
-        if !response.status().is_success() {
-            let err = handle_airtable_error(response, "List records").await;
-            return Err(err);
-        }
+    // Formatting options (`cellFormat`, `timeZone`, `userLocale`)
+    if let Some(cf) = params.cell_format {
+        query.push(("cellFormat".to_string(), cf.as_str().to_string()));
+    }
+    if let Some(ref tz) = params.time_zone {
+        query.push(("timeZone".to_string(), tz.clone()));
+    }
+    if let Some(ref locale) = params.user_locale {
+        query.push(("userLocale".to_string(), locale.clone()));
+    }
 
-        let record_list: RecordList = response.json().await?;
-        all_records.extend(record_list.records);
+    // Key fields by ID if asked for this call or by the client
+    if by_field_id {
+        query.push(("returnFieldsByFieldId".to_string(), "true".to_string()));
+    }
 
-        if let Some(off) = record_list.offset {
-            offset = Some(off);
-        } else {
-            break;
+    query
+}
+
+// JSON body of a POST listRecords request, same params as `list_query`
+fn list_body(params: &ListRecordsParams, by_field_id: bool) -> Map<String, Value> {
+    let mut body = Map::new();
+
+    if let Some(mr) = params.max_records {
+        body.insert("maxRecords".to_string(), json!(mr));
+    }
+    if let Some(ref v) = params.view {
+        body.insert("view".to_string(), json!(v));
+    }
+    if let Some(ref fields_vec) = params.fields {
+        body.insert("fields".to_string(), json!(fields_vec));
+    }
+    if let Some(ref sorts) = params.sort {
+        let sorts: Vec<Value> = sorts
+            .iter()
+            .map(|sort| json!({ "field": sort.field, "direction": sort.direction.as_str() }))
+            .collect();
+        body.insert("sort".to_string(), Value::Array(sorts));
+    }
+    if let Some(ref formula) = params.filter_by_formula {
+        body.insert("filterByFormula".to_string(), json!(formula));
+    }
+    if let Some(ps) = params.page_size {
+        body.insert("pageSize".to_string(), json!(ps));
+    }
+    if let Some(cf) = params.cell_format {
+        body.insert("cellFormat".to_string(), json!(cf.as_str()));
+    }
+    if let Some(ref tz) = params.time_zone {
+        body.insert("timeZone".to_string(), json!(tz));
+    }
+    if let Some(ref locale) = params.user_locale {
+        body.insert("userLocale".to_string(), json!(locale));
+    }
+    if by_field_id {
+        body.insert("returnFieldsByFieldId".to_string(), json!(true));
+    }
+
+    body
+}
+
+// Length of `url` once `query` is appended and encoded
+fn encoded_url_length(url: &str, query: &[(String, String)]) -> usize {
+    match reqwest::Url::parse(url) {
+        Ok(mut parsed) => {
+            if !query.is_empty() {
+                parsed.query_pairs_mut().extend_pairs(query);
+            }
+            parsed.as_str().len()
         }
+        // Let the request itself report the invalid URL
+        Err(_) => url.len(),
     }
+}
 
-    Ok(all_records)
+// Fetches a single page, starting at `offset` if any
+async fn fetch_page(
+    client: &AirtableClient,
+    list_request: &ListRequest,
+    offset: Option<&str>,
+) -> Result<RecordList, AirtableError> {
+    let request = list_request.request(client, offset);
+
+    // Get (or listRecords POST) request
+    let response = client.send(request).await?;
+
+    // Return Error in case of non success code
+    if !response.status().is_success() {
+        let err = handle_airtable_error(response, "List records").await;
+        return Err(err);
+    }
+
+    let record_list: RecordList = response.json().await?;
+
+    Ok(record_list)
 }
 
 /// Fetches a single record by its `record_id` from the specified `table_name`.
-pub async fn get_record(
+pub async fn get_record<T: DeserializeOwned>(
     client: &AirtableClient,
     table_name: &str,
     record_id: &str,
-) -> Result<Record, AirtableError> {
-    let url = format!(
-        "https://api.airtable.com/v0/{base_id}/{table_name}/{record_id}",
-        base_id = client.base_id,
-        table_name = table_name,
-        record_id = record_id
-    );
+    params: &GetRecordParams,
+) -> Result<Record<T>, AirtableError> {
+    let url = client.record_url(table_name, record_id);
 
     // GET request
-    let response = client
+    let mut request = client
         .http_client
         .get(&url)
-        .header("Authorization", format!("Bearer {}", client.api_key))
-        .send()
-        .await?;
+        .header("Authorization", format!("Bearer {}", client.api_key));
+    if params
+        .return_fields_by_field_id
+        .unwrap_or(client.return_fields_by_field_id)
+    {
+        request = request.query(&[("returnFieldsByFieldId", "true")]);
+    }
+    let response = client.send(request).await?;
 
     // Return Error in case of non success code
     if !response.status().is_success() {
@@ -112,120 +350,316 @@ pub async fn get_record(
 
     let record: Record = response.json().await?;
 
-    Ok(record)
+    record.deserialize_fields()
+}
+
+/// Creates records, 10 per request (Airtable's limit).
+pub async fn create_records<T: Serialize + DeserializeOwned>(
+    client: &AirtableClient,
+    table_name: &str,
+    records: &[Record<T>],
+    params: &CreateRecordsParams,
+) -> Result<Vec<Record<T>>, AirtableError> {
+    let records = serialize_records(records)?;
+
+    let write = WriteRequest {
+        method: Method::POST,
+        options: write_options(client, params.typecast, params.return_fields_by_field_id),
+        operation_desc: "Create records",
+        isolate_failures: params.isolate_failures,
+    };
+    let created = write_records_in_chunks(client, table_name, &records, &write, |_| {}).await?;
+
+    deserialize_records(created)
+}
+
+/// Updates records, 10 per request (Airtable's limit).
+/// `params.mode` selects merging (PATCH) or replacing (PUT) their fields.
+pub async fn update_records<T: Serialize + DeserializeOwned>(
+    client: &AirtableClient,
+    table_name: &str,
+    records: &[Record<T>],
+    params: &UpdateRecordsParams,
+) -> Result<Vec<Record<T>>, AirtableError> {
+    let records = serialize_records(records)?;
+
+    let write = WriteRequest {
+        method: params.mode.method(),
+        options: write_options(client, params.typecast, params.return_fields_by_field_id),
+        operation_desc: "Update records",
+        isolate_failures: params.isolate_failures,
+    };
+    let updated = write_records_in_chunks(client, table_name, &records, &write, |_| {}).await?;
+
+    deserialize_records(updated)
+}
+
+/// Creates or updates records matched on `params.fields_to_merge_on`
+/// (`performUpsert`), 10 per request.
+/// If a chunk fails, returns a `PartialUpsertError` with the IDs created and
+/// updated before it.
+pub async fn upsert_records<T: Serialize + DeserializeOwned>(
+    client: &AirtableClient,
+    table_name: &str,
+    records: &[Record<T>],
+    params: &UpsertParams,
+) -> Result<UpsertResult<T>, AirtableError> {
+    params.validate()?;
+    let records = serialize_records(records)?;
+
+    let mut options = write_options(client, params.typecast, params.return_fields_by_field_id);
+    options.insert(
+        "performUpsert".to_string(),
+        json!({ "fieldsToMergeOn": params.fields_to_merge_on }),
+    );
+
+    let mut created_records = Vec::new();
+    let mut updated_records = Vec::new();
+
+    let write = WriteRequest {
+        method: params.mode.method(),
+        options,
+        operation_desc: "Upsert records",
+        isolate_failures: false,
+    };
+    let result = write_records_in_chunks(client, table_name, &records, &write, |json_resp| {
+        // Airtable reports which IDs were created and which were updated
+        created_records.extend(string_list(&json_resp["createdRecords"]));
+        updated_records.extend(string_list(&json_resp["updatedRecords"]));
+    })
+    .await;
+
+    let records = match result {
+        Ok(records) => records,
+        Err(err @ AirtableError::PartialSuccessError { .. }) => {
+            return Err(AirtableError::PartialUpsertError {
+                created_records,
+                updated_records,
+                source: Box::new(err),
+            });
+        }
+        Err(err) => return Err(err),
+    };
+
+    Ok(UpsertResult {
+        records: deserialize_records(records)?,
+        created_records,
+        updated_records,
+    })
+}
+
+// Body options shared by create/update/upsert requests.
+// Both settings fall back to the client defaults when not set for the call.
+fn write_options(
+    client: &AirtableClient,
+    typecast: Option<bool>,
+    return_fields_by_field_id: Option<bool>,
+) -> Map<String, Value> {
+    let mut options = Map::new();
+    if let Some(typecast) = typecast.or(client.typecast) {
+        options.insert("typecast".to_string(), json!(typecast));
+    }
+    if return_fields_by_field_id.unwrap_or(client.return_fields_by_field_id) {
+        options.insert("returnFieldsByFieldId".to_string(), json!(true));
+    }
+    options
 }
 
-pub async fn create_records(
+// How `write_records_in_chunks` sends each chunk
+struct WriteRequest {
+    method: Method,
+    // Added to every request body (e.g. `typecast`, `performUpsert`)
+    options: Map<String, Value>,
+    operation_desc: &'static str,
+    // Split chunks rejected for their content to find the offending records
+    isolate_failures: bool,
+}
+
+// Sends `records` 10 at a time as described by `write`.
+// `on_chunk` sees each successful response body.
+// On failure, returns a `PartialSuccessError` with what was processed so far.
+//
+// With `isolate_failures`, a chunk rejected for its content is split in halves
+// until the rejected records are alone; they are reported as `failures` once
+// every other record was written.
+async fn write_records_in_chunks(
     client: &AirtableClient,
     table_name: &str,
     records: &[Record],
+    write: &WriteRequest,
+    mut on_chunk: impl FnMut(&Value),
 ) -> Result<Vec<Record>, AirtableError> {
-
     let mut processed = Vec::new();
-    let remaining = records.to_vec();
+    let mut failures: Vec<RecordFailure> = Vec::new();
 
-    let mut offset = 0;
+    // Ranges of `records` left to send, in order
+    let mut pending: VecDeque<(usize, usize)> = (0..records.len())
+        .step_by(10)
+        .map(|start| (start, (start + 10).min(records.len())))
+        .collect();
 
     // Build base request
-    let url = format!("https://api.airtable.com/v0/{}/{}", client.base_id, table_name);
+    let url = client.table_url(table_name);
+
+    while let Some((start, end)) = pending.pop_front() {
+        let chunk = &records[start..end];
 
-    while offset < remaining.len() {
-        let chunk_end = (offset + 10).min(remaining.len());
-        let chunk = &remaining[offset..chunk_end];
-        let body = json!({ "records": chunk });
+        let mut body = write.options.clone();
+        body.insert("records".to_string(), json!(chunk));
 
-        // POST request
-        let response = client
+        let request = client
             .http_client
-            .post(&url)
+            .request(write.method.clone(), &url)
             .header("Authorization", format!("Bearer {}", client.api_key))
-            .json(&body)
-            .send()
-            .await?;
+            .json(&body);
+        let response = if write.method == Method::POST {
+            client.send_non_idempotent(request).await?
+        } else {
+            client.send(request).await?
+        };
 
         if !response.status().is_success() {
-            // Construct partial success error
-            let err_message = handle_airtable_error(response, "Create records").await.to_string();
+            let err = handle_airtable_error(response, write.operation_desc).await;
+
+            if write.isolate_failures && is_record_error(&err) {
+                if chunk.len() > 1 {
+                    // Retry both halves before moving on
+                    let mid = start + chunk.len() / 2;
+                    pending.push_front((mid, end));
+                    pending.push_front((start, mid));
+                } else {
+                    failures.push(RecordFailure {
+                        index: start,
+                        record: chunk[0].clone(),
+                        error: err,
+                    });
+                }
+                continue;
+            }
 
+            // Construct partial success error
+            let message = err.to_string();
+            let mut remaining: Vec<Record> = failures.iter().map(|f| f.record.clone()).collect();
+            remaining.extend_from_slice(&records[start..]);
             // Return an error variant with partial success data
-            let processed_count = processed.len();
             return Err(AirtableError::PartialSuccessError {
-                processed_count,
+                processed_count: processed.len(),
                 processed,
-                remaining: remaining[offset..].to_vec(),
-                message: err_message,
+                remaining,
+                message,
+                failures,
             });
         }
 
         // Success: parse the JSON
         let json_resp: Value = response.json().await?;
-        let created_chunk: Vec<Record> = serde_json::from_value(
-            json_resp["records"].clone()
-        )?;
+        let processed_chunk: Vec<Record> =
+            serde_json::from_value(json_resp["records"].clone())?;
+        on_chunk(&json_resp);
 
         // Extend our 'processed' list
-        processed.extend(created_chunk);
+        processed.extend(processed_chunk);
+    }
 
-        // Advance offset to move to the next chunk
-        offset = chunk_end;
+    if !failures.is_empty() {
+        return Err(AirtableError::PartialSuccessError {
+            processed_count: processed.len(),
+            processed,
+            remaining: failures.iter().map(|f| f.record.clone()).collect(),
+            message: format!(
+                "{} of {} records were rejected, first error: {}",
+                failures.len(),
+                records.len(),
+                failures[0].error
+            ),
+            failures,
+        });
     }
 
     // Return the processed records
     Ok(processed)
 }
 
+// Whether Airtable rejected the request because of what some records contain.
+// Errors about the request as a whole (unknown field, bad token, missing table)
+// would fail for every record, so bisecting them only multiplies requests.
+fn is_record_error(err: &AirtableError) -> bool {
+    matches!(
+        err.kind(),
+        Some(
+            ApiErrorKind::InvalidValueForColumn
+                | ApiErrorKind::InvalidMultipleChoiceOptions
+                | ApiErrorKind::InvalidRecords
+                | ApiErrorKind::RowDoesNotExist
+                | ApiErrorKind::CannotUpdateComputedField
+        )
+    )
+}
 
-pub async fn update_records(
+// Strings of a JSON array, ignoring anything else
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Deletes records by ID, 10 per request (Airtable's limit).
+/// Returns the IDs of the deleted records.
+pub async fn delete_records(
     client: &AirtableClient,
     table_name: &str,
-    records: &[Record],
-) -> Result<Vec<Record>, AirtableError> {
-    let mut processed = Vec::new();
-    let remaining = records.to_vec();
+    record_ids: &[String],
+) -> Result<Vec<String>, AirtableError> {
+    let mut deleted = Vec::new();
     let mut offset = 0;
 
-    // Build base request
-    let url = format!("https://api.airtable.com/v0/{}/{}", client.base_id, table_name);
+    let url = client.table_url(table_name);
 
-    while offset < remaining.len() {
-        let chunk_end = (offset + 10).min(remaining.len());
-        let chunk = &remaining[offset..chunk_end];
-        let body = json!({ "records": chunk });
+    while offset < record_ids.len() {
+        let chunk_end = (offset + 10).min(record_ids.len());
+        let chunk = &record_ids[offset..chunk_end];
 
-        // PATCH request
-        let response = client
+        // DELETE request with `records[]=recXXX` for each ID
+        let mut request = client
             .http_client
-            .patch(&url)
-            .header("Authorization", format!("Bearer {}", client.api_key))
-            .json(&body)
-            .send()
-            .await?;
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", client.api_key));
+        for id in chunk {
+            request = request.query(&[("records[]", id)]);
+        }
+        let response = client.send(request).await?;
 
         if !response.status().is_success() {
-            // Construct partial success error
-            let err_message = handle_airtable_error(response, "Update records").await.to_string();
-            let processed_count = processed.len();
-            // Return an error variant with partial success data
-            return Err(AirtableError::PartialSuccessError {
-                processed_count,
-                processed,
-                remaining: remaining[offset..].to_vec(),
+            let err_message = handle_airtable_error(response, "Delete records").await.to_string();
+            let deleted_count = deleted.len();
+            // Return an error variant with the IDs deleted so far
+            return Err(AirtableError::PartialDeleteError {
+                deleted_count,
+                deleted,
+                remaining: record_ids[offset..].to_vec(),
                 message: err_message,
             });
         }
 
-        // Success: parse the JSON
         let json_resp: Value = response.json().await?;
-        let updated_chunk: Vec<Record> =
+        let deleted_chunk: Vec<DeletedRecord> =
             serde_json::from_value(json_resp["records"].clone())?;
 
-        // Extend our 'processed' list
-        processed.extend(updated_chunk);
+        deleted.extend(
+            deleted_chunk
+                .into_iter()
+                .filter(|r| r.deleted)
+                .map(|r| r.id),
+        );
 
-        // Advance offset to move to the next chunk
         offset = chunk_end;
     }
 
-    // Return the processed records
-    Ok(processed)
+    Ok(deleted)
 }
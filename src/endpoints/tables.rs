@@ -0,0 +1,58 @@
+use crate::{
+    client::error::handle_airtable_error,
+    client::{AirtableClient, AirtableError},
+    types::params::UpdateTableParams,
+    types::schema::{Table, TableDefinition},
+};
+
+/// Creates a table in the client's base, with `table.fields` in order
+/// (the first one is the primary field).
+pub async fn create_table(
+    client: &AirtableClient,
+    table: &TableDefinition,
+) -> Result<Table, AirtableError> {
+    table.validate()?;
+    let url = client.meta_url(&format!("bases/{}/tables", client.base_id));
+
+    let request = client
+        .http_client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", client.api_key))
+        .json(table);
+    let response = client.send_non_idempotent(request).await?;
+
+    if !response.status().is_success() {
+        let err = handle_airtable_error(response, "Create table").await;
+        return Err(err);
+    }
+
+    let created: Table = response.json().await?;
+
+    Ok(created)
+}
+
+/// Renames a table and/or changes its description.
+pub async fn update_table(
+    client: &AirtableClient,
+    table_id_or_name: &str,
+    params: &UpdateTableParams,
+) -> Result<Table, AirtableError> {
+    params.validate()?;
+    let url = client.meta_url(&format!("bases/{}/tables/{}", client.base_id, table_id_or_name));
+
+    let request = client
+        .http_client
+        .patch(&url)
+        .header("Authorization", format!("Bearer {}", client.api_key))
+        .json(params);
+    let response = client.send(request).await?;
+
+    if !response.status().is_success() {
+        let err = handle_airtable_error(response, "Update table").await;
+        return Err(err);
+    }
+
+    let updated: Table = response.json().await?;
+
+    Ok(updated)
+}
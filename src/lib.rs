@@ -1,3 +1,17 @@
 pub mod client;
 pub mod endpoints;
-pub mod types;
\ No newline at end of file
+pub mod types;
+#[cfg(feature = "declarative")]
+pub mod declarative;
+
+pub use types::model::AirtableRecord;
+
+/// `#[derive(AirtableRecord)]`, see `types::model::AirtableRecord`
+#[cfg(feature = "derive")]
+pub use airtable_helper_derive::AirtableRecord;
+
+// Used by the code generated by `#[derive(AirtableRecord)]`
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+}
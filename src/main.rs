@@ -1,6 +1,5 @@
 use dotenv::dotenv;
 use reqwest::{self, header, Client};
-use serde::Serialize;
 use std::{collections::HashMap, env};
 use serde_json::{json, Value};
 use tokio::time::{sleep, Duration};
@@ -20,7 +19,7 @@ async fn main() {
     //fields.insert("Status".to_string(), json!("[To Do]"));
     fields.insert("Due".to_string(), json!("2025-03-01T12:00:00.000Z"));
     fields.insert("integer".to_string(), json!(10));
-    fields.insert("float".to_string(), json!(3.1415));
+    fields.insert("float".to_string(), json!(2.5));
     fields.insert("positive_integer".to_string(), json!(7));
     fields.insert("poor_text".to_string(), json!("This is some text."));
     fields.insert("Rich text".to_string(), json!("Rich text example\n\n```\ncode snippet\n```"));
@@ -34,7 +33,9 @@ async fn main() {
     
     sleep(Duration::from_secs(3)).await;
 
-    get_records(&base_id, &table_1_id, &api_key, &Some("recbztNx2W30I2eBA".to_string())).await;
+    if let Err(err) = get_records(&base_id, &table_1_id, &api_key, &Some("recbztNx2W30I2eBA".to_string())).await {
+        println!("Error fetching record: {}", err);
+    }
 
     
     //get_records(&base_id, &table_1_id, &api_key, &None);
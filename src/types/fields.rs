@@ -0,0 +1,176 @@
+//! Typed cell values, matching the JSON the API returns for each field type.
+//!
+//! Use them in typed records, e.g. `Record<Task>` with
+//! `#[serde(rename = "Files")] files: Option<Vec<Attachment>>`.
+//! Airtable omits empty cells, so most fields are best wrapped in `Option`.
+
+use serde::{Deserialize, Serialize};
+
+/// Single line text, long text, rich text (markdown), email, URL and phone number
+pub type Text = String;
+
+/// Single select: the name of the chosen option
+pub type SingleSelect = String;
+
+/// Multiple select: the names of the chosen options
+pub type MultipleSelects = Vec<String>;
+
+/// Link to another record: IDs of the linked records
+pub type LinkedRecords = Vec<String>;
+
+/// Date (`2025-03-01`) or date time (`2025-03-01T12:00:00.000Z`) in ISO 8601
+pub type DateString = String;
+
+/// File attached to a record.
+///
+/// To upload, only `url` (publicly reachable) and optionally `filename` are needed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attachment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    /// Size in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// MIME type, e.g. `image/png`
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Images only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Images and documents only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnails: Option<Thumbnails>,
+}
+
+impl Attachment {
+    /// Attachment to upload from a public URL.
+    pub fn from_url(url: &str, filename: Option<&str>) -> Self {
+        Self {
+            id: None,
+            url: url.to_string(),
+            filename: filename.map(str::to_string),
+            size: None,
+            mime_type: None,
+            width: None,
+            height: None,
+            thumbnails: None,
+        }
+    }
+}
+
+/// Generated previews of an attachment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Thumbnails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub small: Option<Thumbnail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub large: Option<Thumbnail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full: Option<Thumbnail>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Thumbnail {
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// User, collaborator, created by and last modified by fields.
+///
+/// To write, set either `id` or `email`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Collaborator {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission_level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_pic_url: Option<String>,
+}
+
+/// Barcode field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Barcode {
+    pub text: String,
+    /// Symbology, e.g. `upce` or `code39`
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub barcode_type: Option<String>,
+}
+
+/// Button field (read only)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Button {
+    pub label: String,
+    /// URL opened by the button, if it opens one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// AI text field (read only)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiText {
+    pub state: AiTextState,
+    /// Whether the inputs changed since the value was generated
+    pub is_stale: bool,
+    /// Generated text, set when `state` is `Generated`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Set when `state` is `Error`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AiTextState {
+    Empty,
+    Loading,
+    Generated,
+    Error,
+}
+
+/// Rating field, from 1 to the field's max (at most 10). Empty ratings are omitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Rating(pub u8);
+
+/// Duration field, in seconds
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Duration(pub f64);
+
+impl Duration {
+    /// The duration as `std::time::Duration`, or `None` if it is negative,
+    /// NaN or too large.
+    pub fn to_std(self) -> Option<std::time::Duration> {
+        std::time::Duration::try_from_secs_f64(self.0).ok()
+    }
+}
+
+impl From<std::time::Duration> for Duration {
+    fn from(duration: std::time::Duration) -> Self {
+        Self(duration.as_secs_f64())
+    }
+}
+
+/// Currency field: the amount, the symbol is part of the field options
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Currency(pub f64);
+
+/// Percent field, as a fraction (`0.5` is 50%)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Percent(pub f64);
@@ -1,2 +1,5 @@
 pub mod records;
-pub mod params;
\ No newline at end of file
+pub mod params;
+pub mod model;
+pub mod fields;
+pub mod schema;
\ No newline at end of file
@@ -0,0 +1,132 @@
+use crate::client::AirtableError;
+use crate::types::records::Record;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// A struct mapped to an Airtable table, usually implemented with
+/// `#[derive(AirtableRecord)]`.
+///
+/// ```ignore
+/// #[derive(AirtableRecord)]
+/// #[airtable(table = "Tasks")]
+/// struct Task {
+///     #[airtable(field = "Name")]
+///     name: String,
+///     #[airtable(field = "fldStatus123")]   // field IDs work too
+///     status: Option<String>,
+///     #[airtable(field = "Days left", read_only)]  // formula, never sent
+///     days_left: Option<f64>,
+/// }
+///
+/// let tasks = client.list_records::<Task>(Task::TABLE, None).await?;
+/// let changes = Task::changes().status(Some("Done".to_string()));
+/// client.update_records(Task::TABLE, &[changes.into_record("rec123")?], None).await?;
+/// ```
+pub trait AirtableRecord: Serialize + DeserializeOwned {
+    /// Builder of partial updates, with one setter per writable field.
+    type Changes: From<RecordChanges> + Into<RecordChanges>;
+
+    /// Table name or ID.
+    const TABLE: &'static str;
+
+    /// Column (name or field ID) of every struct field, in declaration order.
+    const FIELDS: &'static [&'static str];
+
+    /// Columns that are never sent on writes (formulas, lookups...).
+    const READ_ONLY_FIELDS: &'static [&'static str];
+
+    /// Starts an empty partial update.
+    fn changes() -> Self::Changes {
+        Self::Changes::from(RecordChanges::new())
+    }
+
+    /// Partial update with only the fields that differ between `self` and `updated`.
+    /// Fields that `updated` leaves empty are cleared.
+    fn changes_to(&self, updated: &Self) -> Result<Self::Changes, AirtableError> {
+        let before = fields_map(self)?;
+        let after = fields_map(updated)?;
+
+        let mut changes = RecordChanges::new();
+        for column in Self::FIELDS {
+            if Self::READ_ONLY_FIELDS.contains(column) {
+                continue;
+            }
+            let old = before.get(*column).unwrap_or(&Value::Null);
+            let new = after.get(*column).unwrap_or(&Value::Null);
+            if old != new {
+                changes.set_value(column, new.clone());
+            }
+        }
+
+        Ok(Self::Changes::from(changes))
+    }
+}
+
+// Serializes a model into its JSON fields object
+fn fields_map<T: Serialize>(model: &T) -> Result<Map<String, Value>, AirtableError> {
+    match serde_json::to_value(model)? {
+        Value::Object(map) => Ok(map),
+        other => Err(AirtableError::Other(format!(
+            "Record fields must serialize to a JSON object, got {}",
+            other
+        ))),
+    }
+}
+
+/// Fields to send in a partial update, keyed by column.
+/// Only the fields that were set are sent, so the others keep their values.
+#[derive(Debug, Default)]
+pub struct RecordChanges {
+    fields: Map<String, Value>,
+    // First value that failed to serialize, reported by `into_record`
+    error: Option<serde_json::Error>,
+}
+
+impl RecordChanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `column` to `value`.
+    pub fn set<V: Serialize>(&mut self, column: &str, value: &V) {
+        match serde_json::to_value(value) {
+            Ok(value) => self.set_value(column, value),
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
+    }
+
+    /// Sets `column` to an already serialized value (`Value::Null` clears it).
+    pub fn set_value(&mut self, column: &str, value: Value) {
+        self.fields.insert(column.to_string(), value);
+    }
+
+    /// Whether no field was set.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// The fields set so far.
+    pub fn fields(&self) -> &Map<String, Value> {
+        &self.fields
+    }
+
+    /// Record to pass to `update_records` (in merge mode) for `record_id`.
+    pub fn into_record(self, record_id: &str) -> Result<Record, AirtableError> {
+        if let Some(e) = self.error {
+            return Err(AirtableError::RecordConversion {
+                record_id: Some(record_id.to_string()),
+                field: None,
+                source: e,
+            });
+        }
+
+        Ok(Record {
+            id: Some(record_id.to_string()),
+            fields: Value::Object(self.fields),
+            created_time: None,
+        })
+    }
+}
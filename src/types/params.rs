@@ -1,26 +1,301 @@
-#[derive(Debug)]
+use crate::client::AirtableError;
+use serde::Serialize;
+
+// Default values for these optional params are all `None`.
+#[derive(Debug, Default)]
 pub struct ListRecordsParams {
     pub view: Option<String>,
     pub max_records: Option<u32>,
     pub fields: Option<Vec<String>>,
-    pub sort: Option<(String, String)>,
+    /// Sort specs in priority order, sent as `sort[0]`, `sort[1]`, ...
+    pub sort: Option<Vec<Sort>>,
+    /// Only records for which this formula is truthy are returned,
+    /// e.g. `{Status} = 'Done'`. Sent URL-encoded as `filterByFormula`.
+    pub filter_by_formula: Option<String>,
+    /// Records per page (1 to 100, Airtable defaults to 100).
+    pub page_size: Option<u32>,
+    /// `CellFormat::String` returns values formatted as in the UI;
+    /// it requires `time_zone` and `user_locale`.
+    pub cell_format: Option<CellFormat>,
+    /// Time zone used to format dates with `CellFormat::String`, e.g. `Europe/Paris`.
+    pub time_zone: Option<String>,
+    /// Locale used to format values with `CellFormat::String`, e.g. `fr`.
+    pub user_locale: Option<String>,
+    /// Key returned fields by field ID (`fld...`) instead of name.
+    /// Overrides the client's setting for this call.
+    pub return_fields_by_field_id: Option<bool>,
+    /// Offset cursor to start listing from, taken from `RecordList::offset`
+    /// or a `PartialListError`. Airtable expires offsets after a while.
+    pub offset: Option<String>,
+    /// Always list with `POST .../listRecords` (JSON body) instead of GET.
+    /// POST is used anyway when the GET URL would exceed Airtable's 16k limit,
+    /// e.g. with long formulas or many fields.
+    pub force_post: bool,
+}
+
+impl ListRecordsParams {
+    /// `new()` that just returns the default instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks that `page_size` is between 1 and 100 and that string cell values
+    /// come with a time zone and a locale, as Airtable requires.
+    pub fn validate(&self) -> Result<(), AirtableError> {
+        if let Some(page_size) = self.page_size {
+            if !(1..=100).contains(&page_size) {
+                return Err(AirtableError::InvalidParams(format!(
+                    "page_size must be between 1 and 100, got {}",
+                    page_size
+                )));
+            }
+        }
+
+        if self.cell_format == Some(CellFormat::String)
+            && (self.time_zone.is_none() || self.user_locale.is_none())
+        {
+            return Err(AirtableError::InvalidParams(
+                "cell_format String requires both time_zone and user_locale".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Format of the returned cell values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CellFormat {
+    /// Raw JSON values (Airtable's default)
+    #[default]
+    Json,
+    /// Values formatted as strings, as displayed in the UI
+    String,
+}
+
+impl CellFormat {
+    /// Value expected by the API (`json` / `string`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CellFormat::Json => "json",
+            CellFormat::String => "string",
+        }
+    }
+}
+
+/// Direction of a sort
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    /// Value expected by the API (`asc` / `desc`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
+    }
 }
 
-// Default values for these optional params.
-impl Default for ListRecordsParams {
-    fn default() -> Self {
+/// Sorts listed records by one field
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sort {
+    /// Field name or ID
+    pub field: String,
+    pub direction: SortDirection,
+}
+
+impl Sort {
+    /// Ascending sort on `field`
+    pub fn asc(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            direction: SortDirection::Asc,
+        }
+    }
+
+    /// Descending sort on `field`
+    pub fn desc(field: &str) -> Self {
         Self {
-            view: None,
-            max_records: None,
-            sort: None,
-            fields: None,
+            field: field.to_string(),
+            direction: SortDirection::Desc,
         }
     }
 }
 
-impl ListRecordsParams {
+/// How updated records are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UpdateMode {
+    /// PATCH: only the given fields change, the others are kept.
+    #[default]
+    Merge,
+    /// PUT: the given fields replace the record, unspecified fields are cleared.
+    Replace,
+}
+
+impl UpdateMode {
+    pub(crate) fn method(&self) -> reqwest::Method {
+        match self {
+            UpdateMode::Merge => reqwest::Method::PATCH,
+            UpdateMode::Replace => reqwest::Method::PUT,
+        }
+    }
+}
+
+/// Optional params for fetching a single record
+#[derive(Debug, Clone, Default)]
+pub struct GetRecordParams {
+    /// Key the returned fields by field ID. Overrides the client's setting.
+    pub return_fields_by_field_id: Option<bool>,
+}
+
+impl GetRecordParams {
+    /// `new()` that just returns the default instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Optional params for creating records
+#[derive(Debug, Clone, Default)]
+pub struct CreateRecordsParams {
+    /// Overrides the client's `typecast` default for this call.
+    pub typecast: Option<bool>,
+    /// Key the returned fields by field ID. Overrides the client's setting.
+    pub return_fields_by_field_id: Option<bool>,
+    /// When Airtable rejects a chunk for what some records contain (invalid cell
+    /// values or select options, computed fields), split it to find the offending
+    /// records and keep writing the others. They are reported in
+    /// `PartialSuccessError::failures`. Errors about the whole request, such as an
+    /// unknown field name, still stop the write at once.
+    pub isolate_failures: bool,
+}
+
+impl CreateRecordsParams {
+    /// `new()` that just returns the default instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Optional params for updating records
+#[derive(Debug, Clone, Default)]
+pub struct UpdateRecordsParams {
+    pub mode: UpdateMode,
+    /// Overrides the client's `typecast` default for this call.
+    pub typecast: Option<bool>,
+    /// Key the returned fields by field ID. Overrides the client's setting.
+    pub return_fields_by_field_id: Option<bool>,
+    /// Same as `CreateRecordsParams::isolate_failures`; also reports records
+    /// that no longer exist (`ROW_DOES_NOT_EXIST`) instead of failing the chunk.
+    pub isolate_failures: bool,
+}
+
+impl UpdateRecordsParams {
+    /// `new()` that just returns the default instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Params for upserting records (`performUpsert`)
+#[derive(Debug, Clone)]
+pub struct UpsertParams {
+    /// Fields used to match existing records (1 to 3 field names or IDs).
+    pub fields_to_merge_on: Vec<String>,
+    /// Whether matched records are merged (PATCH) or replaced (PUT).
+    pub mode: UpdateMode,
+    /// Overrides the client's `typecast` default for this call.
+    pub typecast: Option<bool>,
+    /// Key the returned fields by field ID. Overrides the client's setting.
+    pub return_fields_by_field_id: Option<bool>,
+}
+
+impl UpsertParams {
+    /// Upsert records matched on the given fields.
+    pub fn new(fields_to_merge_on: &[&str]) -> Self {
+        Self {
+            fields_to_merge_on: fields_to_merge_on.iter().map(|f| f.to_string()).collect(),
+            mode: UpdateMode::default(),
+            typecast: None,
+            return_fields_by_field_id: None,
+        }
+    }
+
+    /// Checks that there are 1 to 3 merge fields and that none is blank.
+    pub fn validate(&self) -> Result<(), AirtableError> {
+        if self.fields_to_merge_on.is_empty() || self.fields_to_merge_on.len() > 3 {
+            return Err(AirtableError::InvalidParams(
+                "fields_to_merge_on must contain between 1 and 3 fields".to_string(),
+            ));
+        }
+        if self.fields_to_merge_on.iter().any(|f| f.trim().is_empty()) {
+            return Err(AirtableError::InvalidParams(
+                "fields_to_merge_on must not contain empty field names".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Changes to a table's name and description; fields left `None` are kept
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpdateTableParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl UpdateTableParams {
     /// `new()` that just returns the default instance
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Checks that the update changes something and does not blank the table name.
+    pub fn validate(&self) -> Result<(), AirtableError> {
+        if self.name.is_none() && self.description.is_none() {
+            return Err(AirtableError::InvalidParams(
+                "a table update needs a new name or description".to_string(),
+            ));
+        }
+        if self.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+            return Err(AirtableError::InvalidParams("table name must not be empty".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Changes to a field's name and description; fields left `None` are kept
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpdateFieldParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl UpdateFieldParams {
+    /// `new()` that just returns the default instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks that the update changes something and does not blank the field name.
+    pub fn validate(&self) -> Result<(), AirtableError> {
+        if self.name.is_none() && self.description.is_none() {
+            return Err(AirtableError::InvalidParams(
+                "a field update needs a new name or description".to_string(),
+            ));
+        }
+        if self.name.as_deref().is_some_and(|n| n.trim().is_empty()) {
+            return Err(AirtableError::InvalidParams("field name must not be empty".to_string()));
+        }
+        Ok(())
+    }
 }
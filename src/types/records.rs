@@ -1,15 +1,21 @@
+use crate::client::AirtableError;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::fmt;
 
 /// Represents a single record
+///
+/// `fields` is raw JSON by default; any `Serialize`/`Deserialize` type can be
+/// used instead, e.g. `Record<Task>` with a `#[derive(Deserialize)] struct Task`.
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Record {
+pub struct Record<T = Value> {
     /// The unique ID for this record.
-    #[serde(skip_serializing_if = "Option::is_none")] 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
-    
-    // for now fields as json 
-    pub fields: serde_json::Value,
+
+    /// Cell values, keyed by field name (or field ID with `returnFieldsByFieldId`)
+    pub fields: T,
 
     /// Airtable generated value -> createdTime
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -17,23 +23,121 @@ pub struct Record {
     pub created_time: Option<String>,
 }
 
-impl fmt::Display for Record {
+impl<T: Serialize> fmt::Display for Record<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `fmt::Error` would make `to_string()` panic, so describe the failure instead
+        let fields = serde_json::to_string(&self.fields)
+            .unwrap_or_else(|e| format!("<unserializable fields: {}>", e));
         write!(
             f,
             "Record {{ id: {}, fields: {}, created_time: {:?} }}",
             self.id.clone().unwrap_or("".to_string()),
-            self.fields,
+            fields,
             self.created_time
         )
     }
 }
 
+impl Record<Value> {
+    /// Converts the JSON fields into `T`.
+    /// On mismatch, the error names the record ID and the offending field.
+    pub fn deserialize_fields<T: DeserializeOwned>(self) -> Result<Record<T>, AirtableError> {
+        let fields = serde_path_to_error::deserialize(self.fields).map_err(|e| {
+            let path = e.path().to_string();
+            let source = e.into_inner();
+
+            // Missing fields are reported by their parent, the name is in the message
+            let field = if path == "." {
+                missing_field_name(&source)
+            } else {
+                Some(path)
+            };
+
+            AirtableError::RecordConversion {
+                record_id: self.id.clone(),
+                field,
+                source,
+            }
+        })?;
+
+        Ok(Record {
+            id: self.id,
+            fields,
+            created_time: self.created_time,
+        })
+    }
+}
+
+impl<T: Serialize> Record<T> {
+    /// Converts the fields into JSON, as sent to the API.
+    pub fn serialize_fields(&self) -> Result<Record<Value>, AirtableError> {
+        let fields = serde_json::to_value(&self.fields).map_err(|source| {
+            AirtableError::RecordConversion {
+                record_id: self.id.clone(),
+                field: None,
+                source,
+            }
+        })?;
+
+        Ok(Record {
+            id: self.id.clone(),
+            fields,
+            created_time: self.created_time.clone(),
+        })
+    }
+}
+
+// Serde reports missing fields as "missing field `Name`"
+fn missing_field_name(err: &serde_json::Error) -> Option<String> {
+    let message = err.to_string();
+    let rest = message.strip_prefix("missing field `")?;
+    rest.split('`').next().map(str::to_string)
+}
+
 /// Represents the list of records
 #[derive(Debug, Serialize, Deserialize)]
-pub struct RecordList {
-    pub records: Vec<Record>,
+pub struct RecordList<T = Value> {
+    pub records: Vec<Record<T>>,
 
     /// offset for pagination
     pub offset: Option<String>,
-}
\ No newline at end of file
+}
+
+impl RecordList<Value> {
+    /// Converts the fields of every record into `T`.
+    pub fn deserialize_fields<T: DeserializeOwned>(self) -> Result<RecordList<T>, AirtableError> {
+        Ok(RecordList {
+            records: deserialize_records(self.records)?,
+            offset: self.offset,
+        })
+    }
+}
+
+/// Converts the fields of every record into `T`.
+pub fn deserialize_records<T: DeserializeOwned>(
+    records: Vec<Record>,
+) -> Result<Vec<Record<T>>, AirtableError> {
+    records.into_iter().map(Record::deserialize_fields).collect()
+}
+
+/// Converts the fields of every record into JSON.
+pub fn serialize_records<T: Serialize>(records: &[Record<T>]) -> Result<Vec<Record>, AirtableError> {
+    records.iter().map(Record::serialize_fields).collect()
+}
+
+/// Result for a single record of a delete request
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeletedRecord {
+    pub id: String,
+    pub deleted: bool,
+}
+
+/// Result of an upsert: the records plus which of them were created or updated
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpsertResult<T = Value> {
+    pub records: Vec<Record<T>>,
+    /// IDs of the records that did not match an existing one
+    pub created_records: Vec<String>,
+    /// IDs of the existing records that were updated
+    pub updated_records: Vec<String>,
+}
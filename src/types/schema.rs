@@ -0,0 +1,496 @@
+//! Bases, tables, fields and views as described by the Metadata API.
+
+use crate::client::AirtableError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// A base the token can access
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Base {
+    pub id: String,
+    pub name: String,
+    pub permission_level: PermissionLevel,
+}
+
+/// Access level of the token's user on a base
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionLevel {
+    None,
+    Read,
+    Comment,
+    Edit,
+    Create,
+    /// Any level added after this crate was written
+    #[serde(other)]
+    Unknown,
+}
+
+/// One page of `list_bases`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BaseList {
+    pub bases: Vec<Base>,
+
+    /// offset for pagination
+    pub offset: Option<String>,
+}
+
+/// Tables of a base, with their fields and views
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaseSchema {
+    pub tables: Vec<Table>,
+}
+
+impl BaseSchema {
+    /// Table by name or ID.
+    pub fn table(&self, name_or_id: &str) -> Option<&Table> {
+        self.tables
+            .iter()
+            .find(|t| t.id == name_or_id || t.name == name_or_id)
+    }
+}
+
+/// A base returned by `create_base`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreatedBase {
+    pub id: String,
+    #[serde(flatten)]
+    pub schema: BaseSchema,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Table {
+    pub id: String,
+    pub name: String,
+    pub primary_field_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub fields: Vec<Field>,
+    #[serde(default)]
+    pub views: Vec<View>,
+}
+
+impl Table {
+    /// Field by name or ID.
+    pub fn field(&self, name_or_id: &str) -> Option<&Field> {
+        self.fields
+            .iter()
+            .find(|f| f.id == name_or_id || f.name == name_or_id)
+    }
+
+    /// The primary field, if present in `fields`.
+    pub fn primary_field(&self) -> Option<&Field> {
+        self.field(&self.primary_field_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Type and type-specific options
+    #[serde(flatten)]
+    pub config: FieldConfig,
+}
+
+/// Table to create. The first field becomes the primary field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableDefinition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub fields: Vec<FieldDefinition>,
+}
+
+impl TableDefinition {
+    /// Table with only its primary field.
+    pub fn new(name: &str, primary_field: FieldDefinition) -> Self {
+        Self {
+            name: name.to_string(),
+            description: None,
+            fields: vec![primary_field],
+        }
+    }
+
+    /// Adds a field after the existing ones.
+    pub fn with_field(mut self, field: FieldDefinition) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Checks that the table has a name and at least one field, and that field
+    /// names are unique ignoring case. Each field is validated too.
+    pub fn validate(&self) -> Result<(), AirtableError> {
+        if self.name.trim().is_empty() {
+            return Err(AirtableError::InvalidParams("table name must not be empty".to_string()));
+        }
+        if self.fields.is_empty() {
+            return Err(AirtableError::InvalidParams(format!(
+                "table '{}' needs at least a primary field",
+                self.name
+            )));
+        }
+
+        let mut names = HashSet::new();
+        for field in &self.fields {
+            field.validate()?;
+            if !names.insert(field.name.to_lowercase()) {
+                return Err(AirtableError::InvalidParams(format!(
+                    "table '{}' has two fields named '{}'",
+                    self.name, field.name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Field to create
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDefinition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Type and type-specific options
+    #[serde(flatten)]
+    pub config: FieldConfig,
+}
+
+impl FieldDefinition {
+    pub fn new(name: &str, config: FieldConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            description: None,
+            config,
+        }
+    }
+
+    /// Checks that the field has a name and is not a computed type, which the API
+    /// cannot create.
+    pub fn validate(&self) -> Result<(), AirtableError> {
+        if self.name.trim().is_empty() {
+            return Err(AirtableError::InvalidParams("field name must not be empty".to_string()));
+        }
+        if self.config.is_computed() {
+            return Err(AirtableError::InvalidParams(format!(
+                "field '{}': {} fields are computed and cannot be created through the API",
+                self.name,
+                self.config.type_name()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct View {
+    pub id: String,
+    pub name: String,
+    /// `grid`, `form`, `calendar`, `gallery`, `kanban`, `timeline` or `block`
+    #[serde(rename = "type")]
+    pub view_type: String,
+    /// Only returned for grid views when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visible_field_ids: Option<Vec<String>>,
+}
+
+/// Field type with its options, (de)serialized as `{"type": ..., "options": ...}`.
+///
+/// Types without a dedicated variant (rollups, lookups, buttons...) are kept
+/// as `Other` with their raw options.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawFieldConfig", into = "RawFieldConfig")]
+pub enum FieldConfig {
+    SingleLineText,
+    MultilineText,
+    RichText,
+    Email,
+    Url,
+    PhoneNumber,
+    Number(NumberOptions),
+    Percent(NumberOptions),
+    Currency(CurrencyOptions),
+    SingleSelect(SelectOptions),
+    MultipleSelects(SelectOptions),
+    SingleCollaborator,
+    MultipleCollaborators,
+    MultipleRecordLinks(LinkOptions),
+    Date(DateOptions),
+    DateTime(DateTimeOptions),
+    MultipleAttachments,
+    Checkbox(CheckboxOptions),
+    Rating(RatingOptions),
+    Duration(DurationOptions),
+    Barcode,
+    /// Computed by Airtable, cannot be created through the API
+    Formula(FormulaOptions),
+    Other {
+        field_type: String,
+        options: Option<Value>,
+    },
+}
+
+impl FieldConfig {
+    /// The API name of the type, e.g. `singleSelect`.
+    pub fn type_name(&self) -> &str {
+        match self {
+            FieldConfig::SingleLineText => "singleLineText",
+            FieldConfig::MultilineText => "multilineText",
+            FieldConfig::RichText => "richText",
+            FieldConfig::Email => "email",
+            FieldConfig::Url => "url",
+            FieldConfig::PhoneNumber => "phoneNumber",
+            FieldConfig::Number(_) => "number",
+            FieldConfig::Percent(_) => "percent",
+            FieldConfig::Currency(_) => "currency",
+            FieldConfig::SingleSelect(_) => "singleSelect",
+            FieldConfig::MultipleSelects(_) => "multipleSelects",
+            FieldConfig::SingleCollaborator => "singleCollaborator",
+            FieldConfig::MultipleCollaborators => "multipleCollaborators",
+            FieldConfig::MultipleRecordLinks(_) => "multipleRecordLinks",
+            FieldConfig::Date(_) => "date",
+            FieldConfig::DateTime(_) => "dateTime",
+            FieldConfig::MultipleAttachments => "multipleAttachments",
+            FieldConfig::Checkbox(_) => "checkbox",
+            FieldConfig::Rating(_) => "rating",
+            FieldConfig::Duration(_) => "duration",
+            FieldConfig::Barcode => "barcode",
+            FieldConfig::Formula(_) => "formula",
+            FieldConfig::Other { field_type, .. } => field_type,
+        }
+    }
+
+    /// Whether Airtable computes the values (formulas, rollups, lookups,
+    /// created time...), so the field cannot be created or written through the API.
+    pub fn is_computed(&self) -> bool {
+        match self {
+            FieldConfig::Formula(_) => true,
+            FieldConfig::Other { field_type, .. } => COMPUTED_TYPES.contains(&field_type.as_str()),
+            _ => false,
+        }
+    }
+}
+
+// Types whose values are computed by Airtable
+const COMPUTED_TYPES: &[&str] = &[
+    "formula",
+    "rollup",
+    "count",
+    "multipleLookupValues",
+    "autoNumber",
+    "createdTime",
+    "lastModifiedTime",
+    "createdBy",
+    "lastModifiedBy",
+    "button",
+    "aiText",
+    "externalSyncSource",
+];
+
+// Wire format of `FieldConfig`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RawFieldConfig {
+    #[serde(rename = "type")]
+    field_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    options: Option<Value>,
+}
+
+impl TryFrom<RawFieldConfig> for FieldConfig {
+    type Error = serde_json::Error;
+
+    fn try_from(raw: RawFieldConfig) -> Result<Self, Self::Error> {
+        fn options<T: serde::de::DeserializeOwned>(raw: &RawFieldConfig) -> Result<T, serde_json::Error> {
+            serde_json::from_value(raw.options.clone().unwrap_or(Value::Null))
+        }
+
+        Ok(match raw.field_type.as_str() {
+            "singleLineText" => FieldConfig::SingleLineText,
+            "multilineText" => FieldConfig::MultilineText,
+            "richText" => FieldConfig::RichText,
+            "email" => FieldConfig::Email,
+            "url" => FieldConfig::Url,
+            "phoneNumber" => FieldConfig::PhoneNumber,
+            "number" => FieldConfig::Number(options(&raw)?),
+            "percent" => FieldConfig::Percent(options(&raw)?),
+            "currency" => FieldConfig::Currency(options(&raw)?),
+            "singleSelect" => FieldConfig::SingleSelect(options(&raw)?),
+            "multipleSelects" => FieldConfig::MultipleSelects(options(&raw)?),
+            "singleCollaborator" => FieldConfig::SingleCollaborator,
+            "multipleCollaborators" => FieldConfig::MultipleCollaborators,
+            "multipleRecordLinks" => FieldConfig::MultipleRecordLinks(options(&raw)?),
+            "date" => FieldConfig::Date(options(&raw)?),
+            "dateTime" => FieldConfig::DateTime(options(&raw)?),
+            "multipleAttachments" => FieldConfig::MultipleAttachments,
+            "checkbox" => FieldConfig::Checkbox(options(&raw)?),
+            "rating" => FieldConfig::Rating(options(&raw)?),
+            "duration" => FieldConfig::Duration(options(&raw)?),
+            "barcode" => FieldConfig::Barcode,
+            "formula" => FieldConfig::Formula(options(&raw)?),
+            _ => FieldConfig::Other {
+                field_type: raw.field_type,
+                options: raw.options,
+            },
+        })
+    }
+}
+
+impl From<FieldConfig> for RawFieldConfig {
+    fn from(config: FieldConfig) -> Self {
+        fn options<T: Serialize>(options: &T) -> Option<Value> {
+            serde_json::to_value(options).ok()
+        }
+
+        let field_type = config.type_name().to_string();
+        let options = match config {
+            FieldConfig::Number(ref o) | FieldConfig::Percent(ref o) => options(o),
+            FieldConfig::Currency(ref o) => options(o),
+            FieldConfig::SingleSelect(ref o) | FieldConfig::MultipleSelects(ref o) => options(o),
+            FieldConfig::MultipleRecordLinks(ref o) => options(o),
+            FieldConfig::Date(ref o) => options(o),
+            FieldConfig::DateTime(ref o) => options(o),
+            FieldConfig::Checkbox(ref o) => options(o),
+            FieldConfig::Rating(ref o) => options(o),
+            FieldConfig::Duration(ref o) => options(o),
+            FieldConfig::Formula(ref o) => options(o),
+            FieldConfig::Other { options, .. } => options,
+            _ => None,
+        };
+
+        RawFieldConfig { field_type, options }
+    }
+}
+
+/// Number and percent options
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NumberOptions {
+    /// Digits after the decimal point, 0 to 8
+    pub precision: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CurrencyOptions {
+    /// Digits after the decimal point, 0 to 7
+    pub precision: u8,
+    pub symbol: String,
+}
+
+/// Single and multiple select options
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectOptions {
+    pub choices: Vec<SelectChoice>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SelectChoice {
+    /// Set by Airtable, leave empty for new choices
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    /// e.g. `blueLight2`, picked by Airtable when empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl SelectChoice {
+    /// New choice, with an optional color.
+    pub fn new(name: &str, color: Option<&str>) -> Self {
+        Self {
+            id: None,
+            name: name.to_string(),
+            color: color.map(str::to_string),
+        }
+    }
+}
+
+/// Link to another record options
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkOptions {
+    /// Table the field links to
+    pub linked_table_id: String,
+    /// Field of the linked table that links back, set by Airtable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inverse_link_field_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view_id_for_record_selection: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_reversed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefers_single_record_link: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DateOptions {
+    pub date_format: DateFormat,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DateTimeOptions {
+    pub date_format: DateFormat,
+    pub time_format: TimeFormat,
+    /// e.g. `utc`, `client` or `Europe/Paris`
+    pub time_zone: String,
+}
+
+/// Date format: `local`, `friendly`, `us`, `european` or `iso`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateFormat {
+    pub name: String,
+    /// Matching moment.js format, filled in by Airtable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+/// Time format: `12hour` or `24hour`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeFormat {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckboxOptions {
+    /// e.g. `check`, `star` or `heart`
+    pub icon: String,
+    /// e.g. `greenBright`
+    pub color: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatingOptions {
+    pub icon: String,
+    /// Highest rating, 1 to 10
+    pub max: u8,
+    pub color: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DurationOptions {
+    /// e.g. `h:mm` or `h:mm:ss`
+    pub duration_format: String,
+}
+
+/// Formula options, reported by the schema only
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormulaOptions {
+    pub formula: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_valid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referenced_field_ids: Option<Vec<String>>,
+    /// Type of the computed values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Box<FieldConfig>>,
+}
@@ -0,0 +1,2089 @@
+#[cfg(feature = "declarative")]
+use airtable_helper::declarative::{DeclaredSchema, SchemaChange};
+#[cfg(feature = "derive")]
+use airtable_helper::AirtableRecord;
+use airtable_helper::{
+    client::{AirtableClient, AirtableError, ApiErrorKind, RateLimit, RateLimiter, RetryPolicy},
+    types::params::{
+        CellFormat, CreateRecordsParams, GetRecordParams, ListRecordsParams, Sort, SortDirection, UpdateMode, UpdateRecordsParams,
+        UpdateFieldParams, UpdateTableParams, UpsertParams,
+    },
+    types::records::Record,
+    types::schema::{
+        DateFormat, DateOptions, FieldConfig, FieldDefinition, FormulaOptions, LinkOptions, NumberOptions,
+        PermissionLevel, SelectChoice, SelectOptions, TableDefinition,
+    },
+};
+use futures::{StreamExt, TryStreamExt};
+use serde_json::{json, Value};
+use std::time::Duration;
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const BASE_ID: &str = "appTestBase";
+
+// Client pointed at the mock server
+fn mock_client(server: &MockServer) -> AirtableClient {
+    AirtableClient::builder("test-key", BASE_ID)
+        .base_url(&server.uri())
+        .build()
+        .expect("valid mock client")
+}
+
+// Mock client that retries quickly
+fn retrying_client(server: &MockServer, max_attempts: u32) -> AirtableClient {
+    AirtableClient::builder("test-key", BASE_ID)
+        .base_url(&server.uri())
+        .retry_policy(RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            rate_limited_delay: Duration::from_millis(1),
+            jitter: false,
+            ..RetryPolicy::default()
+        })
+        .build()
+        .expect("valid mock client")
+}
+
+fn record(id: &str, name: &str) -> serde_json::Value {
+    json!({ "id": id, "createdTime": "2025-01-01T00:00:00.000Z", "fields": { "Name": name } })
+}
+
+#[test]
+fn builder_rejects_invalid_base_url() {
+    let result = AirtableClient::builder("key", BASE_ID).base_url("not a url").build();
+    assert!(matches!(result, Err(AirtableError::Config(_))));
+
+    let result = AirtableClient::builder("key", BASE_ID).base_url("ftp://example.com").build();
+    assert!(matches!(result, Err(AirtableError::Config(_))));
+}
+
+#[test]
+fn builder_trims_trailing_slash() {
+    let client = AirtableClient::builder("key", BASE_ID)
+        .base_url("http://localhost:1234/v0/")
+        .build()
+        .unwrap();
+    assert_eq!(client.base_url(), "http://localhost:1234/v0");
+}
+
+#[tokio::test]
+async fn list_records_follows_offset_against_mock() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(header("Authorization", "Bearer test-key"))
+        .and(query_param("offset", "page2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec2", "Second")]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec1", "First")],
+            "offset": "page2"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let records = client
+        .list_records::<Value>("Tasks", Some(ListRecordsParams::new()))
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = records.iter().map(|r| r.id.clone().unwrap()).collect();
+    assert_eq!(ids, vec!["rec1", "rec2"]);
+}
+
+#[tokio::test]
+async fn get_record_against_mock() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(record("rec1", "First")))
+        .mount(&server)
+        .await;
+
+    let record = mock_client(&server).get_record::<Value>("Tasks", "rec1", None).await.unwrap();
+    assert_eq!(record.id.as_deref(), Some("rec1"));
+    assert_eq!(record.fields["Name"], "First");
+}
+
+#[tokio::test]
+async fn create_records_against_mock() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(body_json(json!({ "typecast": true, "records": [{ "fields": { "Name": "New" } }] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("recNew", "New")]
+        })))
+        .mount(&server)
+        .await;
+
+    let new_record = Record {
+        id: None,
+        fields: json!({ "Name": "New" }),
+        created_time: None,
+    };
+    let created = mock_client(&server)
+        .create_records("Tasks", &[new_record], None)
+        .await
+        .unwrap();
+    assert_eq!(created[0].id.as_deref(), Some("recNew"));
+}
+
+#[tokio::test]
+async fn api_errors_are_reported_from_mock() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/recMissing", BASE_ID)))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "error": { "type": "NOT_FOUND", "message": "Could not find record" }
+        })))
+        .mount(&server)
+        .await;
+
+    let err = mock_client(&server)
+        .get_record::<Value>("Tasks", "recMissing", None)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("NOT_FOUND"));
+    assert!(err.is_not_found() && !err.is_retryable());
+    assert_eq!(err.kind(), Some(&ApiErrorKind::NotFound));
+}
+
+#[tokio::test]
+async fn api_errors_carry_status_kind_and_operation() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/recX", BASE_ID)))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({ "error": "SOME_NEW_TYPE" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/recY", BASE_ID)))
+        .respond_with(ResponseTemplate::new(503).set_body_string("upstream down"))
+        .mount(&server)
+        .await;
+
+    let client = AirtableClient::builder("test-key", BASE_ID)
+        .base_url(&server.uri())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    let err = client.get_record::<Value>("Tasks", "recX", None).await.unwrap_err();
+    match err {
+        AirtableError::Api { status, ref kind, ref operation, .. } => {
+            assert_eq!(status.as_u16(), 403);
+            assert_eq!(kind, &ApiErrorKind::Unknown("SOME_NEW_TYPE".to_string()));
+            assert_eq!(operation, "Get single record");
+        }
+        other => panic!("expected an API error, got {:?}", other),
+    }
+
+    let err = client.get_record::<Value>("Tasks", "recY", None).await.unwrap_err();
+    assert!(err.is_retryable());
+    assert_eq!(err.kind(), Some(&ApiErrorKind::ServiceUnavailable));
+    assert!(matches!(err, AirtableError::Api { ref message, .. } if message == "upstream down"));
+}
+
+#[test]
+fn retry_backoff_is_exponential_and_capped() {
+    let policy = RetryPolicy {
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(350),
+        jitter: false,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(350));
+
+    let jittered = RetryPolicy { jitter: true, ..policy };
+    let delay = jittered.backoff(2);
+    assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn retries_rate_limited_and_server_errors() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(record("rec1", "First")))
+        .mount(&server)
+        .await;
+
+    let record = retrying_client(&server, 3).get_record::<Value>("Tasks", "rec1", None).await.unwrap();
+    assert_eq!(record.id.as_deref(), Some("rec1"));
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let err = retrying_client(&server, 3)
+        .get_record::<Value>("Tasks", "rec1", None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(500));
+}
+
+#[tokio::test]
+async fn creates_are_not_retried_on_server_errors() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let new_record = Record {
+        id: None,
+        fields: json!({ "Name": "New" }),
+        created_time: None,
+    };
+    let err = retrying_client(&server, 3)
+        .create_records("Tasks", std::slice::from_ref(&new_record), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AirtableError::PartialSuccessError { processed_count: 0, .. }));
+    server.verify().await;
+    server.reset().await;
+
+    // Rate limited creates were never applied, so they are retried
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(429))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "records": [record("rec1", "New")] })))
+        .mount(&server)
+        .await;
+    let created = retrying_client(&server, 3)
+        .create_records("Tasks", std::slice::from_ref(&new_record), None)
+        .await
+        .unwrap();
+    assert_eq!(created.len(), 1);
+    server.reset().await;
+
+    // Opting in retries them like any other request
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(3)
+        .mount(&server)
+        .await;
+    let client = AirtableClient::builder("test-key", BASE_ID)
+        .base_url(&server.uri())
+        .retry_policy(RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            jitter: false,
+            retry_non_idempotent: true,
+            ..RetryPolicy::default()
+        })
+        .build()
+        .unwrap();
+    assert!(client.create_records("Tasks", &[new_record], None).await.is_err());
+}
+
+#[tokio::test]
+async fn retry_after_is_capped_at_max_delay() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "86400"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(record("rec1", "First")))
+        .mount(&server)
+        .await;
+
+    let client = AirtableClient::builder("test-key", BASE_ID)
+        .base_url(&server.uri())
+        .retry_policy(RetryPolicy {
+            max_attempts: 2,
+            max_delay: Duration::from_millis(10),
+            ..RetryPolicy::default()
+        })
+        .build()
+        .unwrap();
+    let record = tokio::time::timeout(Duration::from_secs(5), client.get_record::<Value>("Tasks", "rec1", None))
+        .await
+        .expect("Retry-After should be capped")
+        .unwrap();
+    assert_eq!(record.id.as_deref(), Some("rec1"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limited_without_retry_after_waits_out_the_penalty() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(429))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(record("rec1", "First")))
+        .mount(&server)
+        .await;
+
+    // No idle connections, whose timers would also advance the paused clock
+    let client = AirtableClient::builder("test-key", BASE_ID)
+        .base_url(&server.uri())
+        .http_client(reqwest::Client::builder().pool_max_idle_per_host(0).build().unwrap())
+        .build()
+        .unwrap();
+
+    // Airtable sends no Retry-After, so the default waits its 30 second penalty
+    let start = tokio::time::Instant::now();
+    let record = client.get_record::<Value>("Tasks", "rec1", None).await.unwrap();
+    assert_eq!(record.id.as_deref(), Some("rec1"));
+    assert!(start.elapsed() >= Duration::from_secs(30));
+    assert!(start.elapsed() < Duration::from_secs(31));
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(422))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(retrying_client(&server, 5).get_record::<Value>("Tasks", "rec1", None).await.is_err());
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limiter_spaces_requests_after_burst() {
+    let limiter = RateLimiter::new(RateLimit {
+        requests_per_second: 5.0,
+        burst: 5,
+    })
+    .unwrap();
+    let start = tokio::time::Instant::now();
+
+    // The burst goes through immediately
+    for _ in 0..5 {
+        limiter.acquire(BASE_ID).await;
+    }
+    assert_eq!(start.elapsed(), Duration::ZERO);
+
+    // Then one request every 200ms
+    for _ in 0..5 {
+        limiter.acquire(BASE_ID).await;
+    }
+    assert!(start.elapsed() >= Duration::from_millis(1000));
+    assert!(start.elapsed() < Duration::from_millis(1100));
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limiter_is_keyed_by_base_and_shared_by_clones() {
+    let limiter = RateLimiter::new(RateLimit {
+        requests_per_second: 1.0,
+        burst: 1,
+    })
+    .unwrap();
+    let clone = limiter.clone();
+    let start = tokio::time::Instant::now();
+
+    limiter.acquire("appOne").await;
+    limiter.acquire("appTwo").await;
+    assert_eq!(start.elapsed(), Duration::ZERO);
+
+    // The clone draws from the same exhausted bucket
+    clone.acquire("appOne").await;
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[test]
+fn builder_validates_rate_limit() {
+    let result = AirtableClient::builder("key", BASE_ID)
+        .rate_limit(RateLimit {
+            requests_per_second: 0.0,
+            burst: 5,
+        })
+        .build();
+    assert!(matches!(result, Err(AirtableError::Config(_))));
+
+    // Limiters built directly are checked too, instead of panicking in `acquire`
+    for requests_per_second in [0.0, f64::NAN, f64::INFINITY] {
+        let result = RateLimiter::new(RateLimit { requests_per_second, burst: 5 });
+        assert!(matches!(result, Err(AirtableError::Config(_))));
+    }
+
+    let client = AirtableClient::builder("key", BASE_ID).no_rate_limit().build().unwrap();
+    assert!(client.rate_limiter().is_none());
+}
+
+#[test]
+fn builder_validates_http_settings() {
+    let result = AirtableClient::builder("", BASE_ID).build();
+    assert!(matches!(result, Err(AirtableError::Config(_))));
+
+    let result = AirtableClient::builder("key", BASE_ID)
+        .timeout(Duration::ZERO)
+        .build();
+    assert!(matches!(result, Err(AirtableError::Config(_))));
+
+    let result = AirtableClient::builder("key", BASE_ID)
+        .proxy("::not a proxy::")
+        .build();
+    assert!(matches!(result, Err(AirtableError::Config(_))));
+
+    // Settings for the internal client would be silently ignored with a custom one
+    let result = AirtableClient::builder("key", BASE_ID)
+        .http_client(reqwest::Client::new())
+        .timeout(Duration::from_secs(5))
+        .build();
+    assert!(matches!(result, Err(AirtableError::Config(_))));
+
+    let client = AirtableClient::builder("key", BASE_ID)
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(5))
+        .proxy("http://proxy.internal:3128")
+        .typecast(Some(false))
+        .build()
+        .unwrap();
+    assert_eq!(client.typecast(), Some(false));
+    assert_eq!(client.base_id(), BASE_ID);
+}
+
+#[tokio::test]
+async fn builder_sends_custom_user_agent() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .and(header("User-Agent", "sync-job/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(record("rec1", "First")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AirtableClient::builder("test-key", BASE_ID)
+        .base_url(&server.uri())
+        .user_agent("sync-job/1.0")
+        .timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    client.get_record::<Value>("Tasks", "rec1", None).await.unwrap();
+}
+
+#[tokio::test]
+async fn delete_records_batches_ids_by_ten() {
+    let server = MockServer::start().await;
+    let ids: Vec<String> = (0..12).map(|i| format!("rec{}", i)).collect();
+
+    Mock::given(method("DELETE"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("records[]", "rec0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": ids[..10].iter().map(|id| json!({ "id": id, "deleted": true })).collect::<Vec<_>>()
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("records[]", "rec10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": ids[10..].iter().map(|id| json!({ "id": id, "deleted": true })).collect::<Vec<_>>()
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let deleted = mock_client(&server).delete_records("Tasks", &ids).await.unwrap();
+    assert_eq!(deleted, ids);
+}
+
+#[tokio::test]
+async fn delete_records_reports_partial_success() {
+    let server = MockServer::start().await;
+    let ids: Vec<String> = (0..12).map(|i| format!("rec{}", i)).collect();
+
+    Mock::given(method("DELETE"))
+        .and(query_param("records[]", "rec0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": ids[..10].iter().map(|id| json!({ "id": id, "deleted": true })).collect::<Vec<_>>()
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(query_param("records[]", "rec10"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "error": { "type": "NOT_FOUND", "message": "Record not found" }
+        })))
+        .mount(&server)
+        .await;
+
+    match mock_client(&server).delete_records("Tasks", &ids).await {
+        Err(AirtableError::PartialDeleteError {
+            deleted,
+            remaining,
+            deleted_count,
+            ..
+        }) => {
+            assert_eq!(deleted_count, 10);
+            assert_eq!(deleted, ids[..10]);
+            assert_eq!(remaining, ids[10..]);
+        }
+        other => panic!("expected partial delete error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn upsert_records_reports_created_and_updated_ids() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(body_json(json!({
+            "performUpsert": { "fieldsToMergeOn": ["Name"] },
+            "typecast": true,
+            "records": [{ "fields": { "Name": "First" } }, { "fields": { "Name": "New" } }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec1", "First"), record("rec2", "New")],
+            "createdRecords": ["rec2"],
+            "updatedRecords": ["rec1"]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let records = vec![
+        Record { id: None, fields: json!({ "Name": "First" }), created_time: None },
+        Record { id: None, fields: json!({ "Name": "New" }), created_time: None },
+    ];
+    let result = mock_client(&server)
+        .upsert_records("Tasks", &records, UpsertParams::new(&["Name"]))
+        .await
+        .unwrap();
+
+    assert_eq!(result.records.len(), 2);
+    assert_eq!(result.created_records, vec!["rec2"]);
+    assert_eq!(result.updated_records, vec!["rec1"]);
+}
+
+#[tokio::test]
+async fn upsert_records_keeps_created_and_updated_ids_on_failure() {
+    let server = MockServer::start().await;
+
+    // The first chunk goes through, the second one is rejected
+    Mock::given(method("PATCH"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(|request: &wiremock::Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let records = body["records"].as_array().unwrap();
+            if records.len() < 10 {
+                return ResponseTemplate::new(422).set_body_json(json!({
+                    "error": { "type": "INVALID_VALUE_FOR_COLUMN", "message": "Bad value" }
+                }));
+            }
+            let upserted: Vec<Value> = (0..10).map(|i| record(&format!("rec{}", i), "Task")).collect();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "records": upserted,
+                "createdRecords": ["rec0", "rec1"],
+                "updatedRecords": ["rec2", "rec3", "rec4", "rec5", "rec6", "rec7", "rec8", "rec9"]
+            }))
+        })
+        .mount(&server)
+        .await;
+
+    let records: Vec<Record> = (0..12)
+        .map(|i| Record { id: None, fields: json!({ "Name": format!("Task {}", i) }), created_time: None })
+        .collect();
+    let err = mock_client(&server)
+        .upsert_records("Tasks", &records, UpsertParams::new(&["Name"]))
+        .await
+        .unwrap_err();
+
+    let AirtableError::PartialUpsertError { created_records, updated_records, source } = err else {
+        panic!("expected a partial upsert error, got {:?}", err);
+    };
+    assert_eq!(created_records, vec!["rec0", "rec1"]);
+    assert_eq!(updated_records.len(), 8);
+    assert!(matches!(
+        *source,
+        AirtableError::PartialSuccessError { processed_count: 10, ref remaining, .. } if remaining.len() == 2
+    ));
+}
+
+#[tokio::test]
+async fn create_records_isolates_rejected_records() {
+    let server = MockServer::start().await;
+
+    // Rejects any request containing a record named "Bad", echoes the others
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(|request: &wiremock::Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let records = body["records"].as_array().unwrap();
+            if records.iter().any(|r| r["fields"]["Name"] == "Bad") {
+                return ResponseTemplate::new(422).set_body_json(json!({
+                    "error": { "type": "INVALID_VALUE_FOR_COLUMN", "message": "Bad value" }
+                }));
+            }
+            let created: Vec<Value> = records
+                .iter()
+                .map(|r| record("recNew", r["fields"]["Name"].as_str().unwrap()))
+                .collect();
+            ResponseTemplate::new(200).set_body_json(json!({ "records": created }))
+        })
+        .mount(&server)
+        .await;
+
+    let records: Vec<Record> = (0..12)
+        .map(|i| Record {
+            id: None,
+            fields: json!({ "Name": if i == 3 || i == 11 { "Bad".to_string() } else { format!("Task {}", i) } }),
+            created_time: None,
+        })
+        .collect();
+    let params = CreateRecordsParams {
+        isolate_failures: true,
+        ..Default::default()
+    };
+
+    let err = mock_client(&server)
+        .create_records("Tasks", &records, Some(params))
+        .await
+        .unwrap_err();
+    let AirtableError::PartialSuccessError { processed, remaining, failures, .. } = err else {
+        panic!("expected a partial success error, got {:?}", err);
+    };
+
+    assert_eq!(processed.len(), 10);
+    assert_eq!(processed[3].fields["Name"], "Task 4");
+    assert_eq!(remaining.len(), 2);
+    let indexes: Vec<usize> = failures.iter().map(|f| f.index).collect();
+    assert_eq!(indexes, vec![3, 11]);
+    assert_eq!(failures[0].error.kind(), Some(&ApiErrorKind::InvalidValueForColumn));
+
+    // Without the option, the first rejected chunk stops the load
+    let err = mock_client(&server)
+        .create_records("Tasks", &records, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        AirtableError::PartialSuccessError { processed_count: 0, ref failures, ref remaining, .. }
+            if failures.is_empty() && remaining.len() == 12
+    ));
+}
+
+#[tokio::test]
+async fn isolate_failures_does_not_bisect_whole_request_errors() {
+    let server = MockServer::start().await;
+
+    // An unknown field fails every record, so splitting the chunk is pointless
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+            "error": { "type": "UNKNOWN_FIELD_NAME", "message": "Unknown field name: \"Nmae\"" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let records: Vec<Record> = (0..10)
+        .map(|i| Record {
+            id: None,
+            fields: json!({ "Nmae": format!("Task {}", i) }),
+            created_time: None,
+        })
+        .collect();
+    let params = CreateRecordsParams {
+        isolate_failures: true,
+        ..Default::default()
+    };
+
+    let err = mock_client(&server)
+        .create_records("Tasks", &records, Some(params))
+        .await
+        .unwrap_err();
+    let AirtableError::PartialSuccessError { processed_count, remaining, failures, .. } = err else {
+        panic!("expected a partial success error, got {:?}", err);
+    };
+    assert_eq!(processed_count, 0);
+    assert_eq!(remaining.len(), 10);
+    assert!(failures.is_empty());
+}
+
+#[tokio::test]
+async fn upsert_records_validates_merge_fields() {
+    let server = MockServer::start().await;
+    let err = mock_client(&server)
+        .upsert_records::<Value>("Tasks", &[], UpsertParams::new(&[]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AirtableError::InvalidParams(_)));
+}
+
+#[tokio::test]
+async fn update_records_merges_or_replaces() {
+    let server = MockServer::start().await;
+    let records: Vec<Record> = (0..12)
+        .map(|i| Record {
+            id: Some(format!("rec{}", i)),
+            fields: json!({ "Name": "Same" }),
+            created_time: None,
+        })
+        .collect();
+
+    // Default mode is PATCH, still chunked by 10
+    Mock::given(method("PATCH"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec0", "Same")]
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+            "error": { "type": "INVALID_VALUE_FOR_COLUMN", "message": "Bad value" }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let updated = client.update_records("Tasks", &records, None).await.unwrap();
+    assert_eq!(updated.len(), 2);
+
+    let params = UpdateRecordsParams {
+        mode: UpdateMode::Replace,
+        ..UpdateRecordsParams::default()
+    };
+    let err = client
+        .update_records("Tasks", &records, Some(params))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        AirtableError::PartialSuccessError { processed_count: 0, ref remaining, .. } if remaining.len() == 12
+    ));
+}
+
+#[tokio::test]
+async fn typecast_uses_client_default_and_per_call_override() {
+    let server = MockServer::start().await;
+
+    // Client default disabled, overridden to true for the call
+    Mock::given(method("POST"))
+        .and(body_json(json!({ "typecast": true, "records": [{ "fields": { "Status": "New option" } }] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "records": [record("rec1", "x")] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // Client default applied to updates
+    Mock::given(method("PATCH"))
+        .and(body_json(json!({ "typecast": false, "records": [{ "id": "rec1", "fields": { "Status": "New option" } }] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "records": [record("rec1", "x")] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AirtableClient::builder("test-key", BASE_ID)
+        .base_url(&server.uri())
+        .typecast(Some(false))
+        .build()
+        .unwrap();
+
+    let new_record = Record {
+        id: None,
+        fields: json!({ "Status": "New option" }),
+        created_time: None,
+    };
+    let params = CreateRecordsParams {
+        typecast: Some(true),
+        ..CreateRecordsParams::default()
+    };
+    client.create_records("Tasks", &[new_record], Some(params)).await.unwrap();
+
+    let existing = Record {
+        id: Some("rec1".to_string()),
+        fields: json!({ "Status": "New option" }),
+        created_time: None,
+    };
+    client.update_records("Tasks", &[existing], None).await.unwrap();
+}
+
+#[tokio::test]
+async fn list_records_sends_encoded_filter_by_formula() {
+    let server = MockServer::start().await;
+    let formula = r#"AND({Status} = 'Done', FIND("café & co", {Name}) > 0, {Score} >= 1+1)"#;
+
+    // The matcher compares the decoded value, so this checks the round trip
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("filterByFormula", formula))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec1", "First")]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut params = ListRecordsParams::new();
+    params.filter_by_formula = Some(formula.to_string());
+    let records = mock_client(&server)
+        .list_records::<Value>("Tasks", Some(params))
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 1);
+
+    // Reserved characters must not leak into the raw query string
+    let requests = server.received_requests().await.unwrap();
+    let raw_query = requests[0].url.query().unwrap().to_string();
+    assert!(raw_query.starts_with("filterByFormula="));
+    assert!(!raw_query.contains(['{', '}', ' ', '&', '\'', '"', 'é']));
+}
+
+#[tokio::test]
+async fn list_records_sends_every_sort_spec_in_order() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("sort[0][field]", "Status"))
+        .and(query_param("sort[0][direction]", "asc"))
+        .and(query_param("sort[1][field]", "Due"))
+        .and(query_param("sort[1][direction]", "desc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "records": [] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut params = ListRecordsParams::new();
+    params.sort = Some(vec![
+        Sort::asc("Status"),
+        Sort {
+            field: "Due".to_string(),
+            direction: SortDirection::Desc,
+        },
+    ]);
+    mock_client(&server)
+        .list_records::<Value>("Tasks", Some(params))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn list_records_sends_page_size_and_string_format() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("pageSize", "20"))
+        .and(query_param("cellFormat", "string"))
+        .and(query_param("timeZone", "Europe/Paris"))
+        .and(query_param("userLocale", "fr"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "records": [] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut params = ListRecordsParams::new();
+    params.page_size = Some(20);
+    params.cell_format = Some(CellFormat::String);
+    params.time_zone = Some("Europe/Paris".to_string());
+    params.user_locale = Some("fr".to_string());
+    mock_client(&server)
+        .list_records::<Value>("Tasks", Some(params))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn list_records_rejects_invalid_options_before_sending() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "records": [] })))
+        .expect(0)
+        .mount(&server)
+        .await;
+    let client = mock_client(&server);
+
+    let mut params = ListRecordsParams::new();
+    params.cell_format = Some(CellFormat::String);
+    params.time_zone = Some("Europe/Paris".to_string());
+    let err = client.list_records::<Value>("Tasks", Some(params)).await.unwrap_err();
+    assert!(matches!(err, AirtableError::InvalidParams(_)));
+
+    let mut params = ListRecordsParams::new();
+    params.page_size = Some(101);
+    let err = client.list_records::<Value>("Tasks", Some(params)).await.unwrap_err();
+    assert!(matches!(err, AirtableError::InvalidParams(_)));
+}
+
+#[tokio::test]
+async fn return_fields_by_field_id_on_reads_and_writes() {
+    let server = MockServer::start().await;
+    let by_id = json!({ "id": "rec1", "createdTime": "2025-01-01T00:00:00.000Z", "fields": { "fldName": "First" } });
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .and(query_param("returnFieldsByFieldId", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(by_id.clone()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("returnFieldsByFieldId", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "records": [by_id.clone()] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // Writing by field ID
+    Mock::given(method("PATCH"))
+        .and(body_json(json!({
+            "typecast": true,
+            "returnFieldsByFieldId": true,
+            "records": [{ "id": "rec1", "fields": { "fldName": "First" } }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "records": [by_id.clone()] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AirtableClient::builder("test-key", BASE_ID)
+        .base_url(&server.uri())
+        .return_fields_by_field_id(true)
+        .build()
+        .unwrap();
+
+    let fetched = client.get_record::<Value>("Tasks", "rec1", None).await.unwrap();
+    assert_eq!(fetched.fields["fldName"], "First");
+
+    let listed = client.list_records::<Value>("Tasks", None).await.unwrap();
+    assert_eq!(listed[0].fields["fldName"], "First");
+
+    let update = Record {
+        id: Some("rec1".to_string()),
+        fields: json!({ "fldName": "First" }),
+        created_time: None,
+    };
+    client.update_records("Tasks", &[update], None).await.unwrap();
+}
+
+#[tokio::test]
+async fn list_params_override_client_field_id_setting() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("returnFieldsByFieldId", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "records": [] })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut params = ListRecordsParams::new();
+    params.return_fields_by_field_id = Some(true);
+    mock_client(&server).list_records::<Value>("Tasks", Some(params)).await.unwrap();
+}
+
+#[tokio::test]
+async fn get_params_override_client_field_id_setting() {
+    let server = MockServer::start().await;
+    let by_id = json!({ "id": "rec1", "createdTime": "2025-01-01T00:00:00.000Z", "fields": { "fldName": "First" } });
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .and(query_param("returnFieldsByFieldId", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(by_id))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = GetRecordParams {
+        return_fields_by_field_id: Some(true),
+    };
+    let record = mock_client(&server)
+        .get_record::<Value>("Tasks", "rec1", Some(params))
+        .await
+        .unwrap();
+    assert_eq!(record.fields["fldName"], "First");
+}
+
+// Mounts a table served in 3 pages of 2 records
+async fn mount_three_pages(server: &MockServer) {
+    for (offset, next, ids) in [
+        (Some("p2"), Some("p3"), ["rec3", "rec4"]),
+        (Some("p3"), None, ["rec5", "rec6"]),
+    ] {
+        let mut body = json!({ "records": [record(ids[0], "x"), record(ids[1], "x")] });
+        if let Some(next) = next {
+            body["offset"] = json!(next);
+        }
+        Mock::given(method("GET"))
+            .and(path(format!("/{}/Tasks", BASE_ID)))
+            .and(query_param("offset", offset.unwrap()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec1", "x"), record("rec2", "x")],
+            "offset": "p2"
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn stream_records_yields_every_page() {
+    let server = MockServer::start().await;
+    mount_three_pages(&server).await;
+    let client = mock_client(&server);
+
+    let records: Vec<Record> = client.stream_records::<Value>("Tasks", None).try_collect().await.unwrap();
+    let ids: Vec<_> = records.iter().map(|r| r.id.clone().unwrap()).collect();
+    assert_eq!(ids, vec!["rec1", "rec2", "rec3", "rec4", "rec5", "rec6"]);
+
+    let pages: Vec<_> = client.stream_pages::<Value>("Tasks", None).try_collect().await.unwrap();
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[0].offset.as_deref(), Some("p2"));
+    assert!(pages[2].offset.is_none());
+}
+
+#[tokio::test]
+async fn stream_records_stops_requesting_when_dropped() {
+    let server = MockServer::start().await;
+    mount_three_pages(&server).await;
+    let client = mock_client(&server);
+
+    let first_three: Vec<_> = client.stream_records::<Value>("Tasks", None).take(3).collect().await;
+    assert_eq!(first_three.len(), 3);
+
+    // Only the two pages needed for three records were requested
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn stream_records_reports_invalid_params_as_first_item() {
+    let server = MockServer::start().await;
+    let mut params = ListRecordsParams::new();
+    params.page_size = Some(0);
+
+    let client = mock_client(&server);
+    let mut stream = std::pin::pin!(client.stream_records::<Value>("Tasks", Some(params)));
+    assert!(matches!(stream.next().await, Some(Err(AirtableError::InvalidParams(_)))));
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn list_records_surfaces_partial_results_and_resumes() {
+    let server = MockServer::start().await;
+
+    // Page 2 fails once, then succeeds
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("offset", "p2"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "error": { "type": "INVALID_PERMISSIONS", "message": "Forbidden" }
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    mount_three_pages(&server).await;
+    let client = mock_client(&server);
+
+    let err = client.list_records::<Value>("Tasks", None).await.unwrap_err();
+    let AirtableError::PartialListError { records, offset, source } = err else {
+        panic!("expected partial list error, got {:?}", err);
+    };
+    assert_eq!(records.len(), 2);
+    assert_eq!(offset.as_deref(), Some("p2"));
+    assert!(source.to_string().contains("INVALID_PERMISSIONS"));
+
+    // Resume from the last good offset
+    let mut params = ListRecordsParams::new();
+    params.offset = offset;
+    let rest = client.list_records::<Value>("Tasks", Some(params)).await.unwrap();
+    let ids: Vec<_> = rest.iter().map(|r| r.id.clone().unwrap()).collect();
+    assert_eq!(ids, vec!["rec3", "rec4", "rec5", "rec6"]);
+}
+
+#[tokio::test]
+async fn list_records_page_returns_cursor() {
+    let server = MockServer::start().await;
+    mount_three_pages(&server).await;
+    let client = mock_client(&server);
+
+    let first = client.list_records_page::<Value>("Tasks", None).await.unwrap();
+    assert_eq!(first.records.len(), 2);
+
+    let mut params = ListRecordsParams::new();
+    params.offset = first.offset;
+    let second = client.list_records_page::<Value>("Tasks", Some(params)).await.unwrap();
+    assert_eq!(second.records[0].id.as_deref(), Some("rec3"));
+    assert_eq!(second.offset.as_deref(), Some("p3"));
+}
+
+#[tokio::test]
+async fn expired_offset_has_its_own_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("offset", "stale"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+            "error": { "type": "LIST_RECORDS_ITERATOR_NOT_AVAILABLE", "message": "Iterator expired" }
+        })))
+        .mount(&server)
+        .await;
+
+    let mut params = ListRecordsParams::new();
+    params.offset = Some("stale".to_string());
+    let err = mock_client(&server)
+        .list_records_page::<Value>("Tasks", Some(params))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AirtableError::OffsetExpired(_)));
+}
+
+#[tokio::test]
+async fn list_records_does_not_offer_to_resume_an_expired_offset() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .and(query_param("offset", "p2"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+            "error": { "type": "LIST_RECORDS_ITERATOR_NOT_AVAILABLE", "message": "Iterator expired" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec1", "x")],
+            "offset": "p2"
+        })))
+        .mount(&server)
+        .await;
+
+    // The expired cursor is not handed back in a `PartialListError`
+    let err = mock_client(&server).list_records::<Value>("Tasks", None).await.unwrap_err();
+    assert!(matches!(err, AirtableError::OffsetExpired(_)));
+}
+
+#[tokio::test]
+async fn list_records_switches_to_post_for_long_queries() {
+    let server = MockServer::start().await;
+    let fields: Vec<String> = (0..800).map(|i| format!("Field number {}", i)).collect();
+
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks/listRecords", BASE_ID)))
+        .and(body_json(json!({ "fields": fields, "pageSize": 50 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec1", "First")]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut params = ListRecordsParams::new();
+    params.fields = Some(fields.clone());
+    params.page_size = Some(50);
+    let records = mock_client(&server).list_records::<Value>("Tasks", Some(params)).await.unwrap();
+    assert_eq!(records.len(), 1);
+}
+
+#[tokio::test]
+async fn forced_post_listing_sends_offset_in_body() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks/listRecords", BASE_ID)))
+        .and(body_json(json!({
+            "filterByFormula": "{Status} = 'Done'",
+            "sort": [{ "field": "Name", "direction": "desc" }],
+            "offset": "p2"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec2", "Second")]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("/{}/Tasks/listRecords", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [record("rec1", "First")],
+            "offset": "p2"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut params = ListRecordsParams::new();
+    params.force_post = true;
+    params.filter_by_formula = Some("{Status} = 'Done'".to_string());
+    params.sort = Some(vec![Sort::desc("Name")]);
+    let records = mock_client(&server).list_records::<Value>("Tasks", Some(params)).await.unwrap();
+    let ids: Vec<_> = records.iter().map(|r| r.id.clone().unwrap()).collect();
+    assert_eq!(ids, vec!["rec1", "rec2"]);
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Task {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Estimate", default, skip_serializing_if = "Option::is_none")]
+    estimate: Option<u32>,
+}
+
+#[tokio::test]
+async fn typed_records_round_trip() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [
+                { "id": "rec1", "fields": { "Name": "Write docs", "Estimate": 3 } },
+                { "id": "rec2", "fields": { "Name": "Review" } }
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_json(json!({ "typecast": true, "records": [{ "fields": { "Name": "Ship" } }] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [{ "id": "rec3", "createdTime": "2025-01-01T00:00:00.000Z", "fields": { "Name": "Ship" } }]
+        })))
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let tasks = client.list_records::<Task>("Tasks", None).await.unwrap();
+    assert_eq!(tasks[0].fields.estimate, Some(3));
+    assert_eq!(tasks[1].fields.name, "Review");
+
+    let new_task = Record {
+        id: None,
+        fields: Task { name: "Ship".to_string(), estimate: None },
+        created_time: None,
+    };
+    let created = client.create_records("Tasks", &[new_task], None).await.unwrap();
+    assert_eq!(created[0].id.as_deref(), Some("rec3"));
+    assert_eq!(created[0].fields.name, "Ship");
+}
+
+#[tokio::test]
+async fn typed_record_mismatch_names_record_and_field() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec1", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "rec1", "fields": { "Name": "Write docs", "Estimate": "three" }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks/rec2", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "rec2", "fields": {}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+
+    let err = client.get_record::<Task>("Tasks", "rec1", None).await.unwrap_err();
+    match err {
+        AirtableError::RecordConversion { ref record_id, ref field, .. } => {
+            assert_eq!(record_id.as_deref(), Some("rec1"));
+            assert_eq!(field.as_deref(), Some("Estimate"));
+        }
+        other => panic!("expected conversion error, got {:?}", other),
+    }
+    assert!(err.to_string().contains("rec1") && err.to_string().contains("Estimate"));
+
+    let err = client.get_record::<Task>("Tasks", "rec2", None).await.unwrap_err();
+    assert!(matches!(
+        err,
+        AirtableError::RecordConversion { ref field, .. } if field.as_deref() == Some("Name")
+    ));
+}
+
+#[test]
+fn record_display_survives_unserializable_fields() {
+    // JSON object keys must be strings
+    let fields: std::collections::BTreeMap<Vec<u8>, u8> = [(vec![1], 1)].into_iter().collect();
+    let record = Record { id: Some("rec1".to_string()), fields, created_time: None };
+    assert!(record.to_string().contains("<unserializable fields: "));
+}
+
+#[cfg(feature = "derive")]
+#[derive(Debug, Clone, PartialEq, AirtableRecord)]
+#[airtable(table = "Tasks")]
+struct TaskModel {
+    #[airtable(field = "Name")]
+    name: String,
+    #[airtable(field = "fldStatus")]
+    status: Option<String>,
+    #[airtable(field = "Done", default)]
+    done: bool,
+    #[airtable(field = "Days left", read_only)]
+    days_left: Option<f64>,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_model_maps_columns() {
+    assert_eq!(TaskModel::TABLE, "Tasks");
+    assert_eq!(TaskModel::FIELDS, &["Name", "fldStatus", "Done", "Days left"]);
+    assert_eq!(TaskModel::READ_ONLY_FIELDS, &["Days left"]);
+
+    // Empty cells are omitted by Airtable
+    let task: TaskModel = serde_json::from_value(json!({ "Name": "Write docs", "Days left": 2.5 })).unwrap();
+    assert_eq!(
+        task,
+        TaskModel { name: "Write docs".to_string(), status: None, done: false, days_left: Some(2.5) }
+    );
+
+    // Read-only and empty optional fields are never sent
+    assert_eq!(serde_json::to_value(&task).unwrap(), json!({ "Name": "Write docs", "Done": false }));
+}
+
+// Raw identifiers map to the column without the `r#` prefix
+#[cfg(feature = "derive")]
+#[derive(Debug, AirtableRecord)]
+struct Asset {
+    r#type: String,
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_model_unraws_field_names() {
+    assert_eq!(Asset::TABLE, "Asset");
+    assert_eq!(Asset::FIELDS, &["type"]);
+    let asset: Asset = serde_json::from_value(json!({ "type": "Laptop" })).unwrap();
+    assert_eq!(asset.r#type, "Laptop");
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_changes_only_send_changed_fields() {
+    let changes = TaskModel::changes().status(Some("Done".to_string())).done(true);
+    let record = changes.into_record("rec1").unwrap();
+    assert_eq!(record.id.as_deref(), Some("rec1"));
+    assert_eq!(record.fields, json!({ "fldStatus": "Done", "Done": true }));
+
+    let before = TaskModel {
+        name: "Write docs".to_string(),
+        status: Some("Todo".to_string()),
+        done: false,
+        days_left: Some(1.0),
+    };
+    let after = TaskModel {
+        status: None,
+        days_left: Some(9.0),
+        ..before.clone()
+    };
+    let record = before.changes_to(&after).unwrap().into_record("rec1").unwrap();
+    // Cleared status is sent as null, the read-only change is ignored
+    assert_eq!(record.fields, json!({ "fldStatus": null }));
+
+    assert!(before.changes_to(&before).unwrap().is_empty());
+}
+
+#[cfg(feature = "derive")]
+#[tokio::test]
+async fn derived_model_lists_and_updates() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/Tasks", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [{ "id": "rec1", "fields": { "Name": "Write docs", "fldStatus": "Todo" } }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(body_json(json!({ "typecast": true, "records": [{ "id": "rec1", "fields": { "Done": true } }] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "records": [{ "id": "rec1", "fields": { "Name": "Write docs", "fldStatus": "Todo", "Done": true } }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let tasks = client.list_records::<TaskModel>(TaskModel::TABLE, None).await.unwrap();
+    assert_eq!(tasks[0].fields.status.as_deref(), Some("Todo"));
+
+    let update = TaskModel::changes().done(true).into_record("rec1").unwrap();
+    let updated = client.update_records(TaskModel::TABLE, &[update], None).await.unwrap();
+    assert_eq!(updated[0].fields["Done"], true);
+}
+
+#[test]
+fn field_values_round_trip_api_json() {
+    use airtable_helper::types::fields::{
+        AiText, AiTextState, Attachment, Barcode, Button, Collaborator, Currency, Duration, Rating,
+    };
+
+    let attachment_json = json!({
+        "id": "attXXX",
+        "url": "https://dl.airtable.com/logo.png",
+        "filename": "logo.png",
+        "size": 1024,
+        "type": "image/png",
+        "width": 64,
+        "height": 32,
+        "thumbnails": {
+            "small": { "url": "https://dl.airtable.com/s.png", "width": 36, "height": 18 },
+            "large": { "url": "https://dl.airtable.com/l.png", "width": 64, "height": 32 }
+        }
+    });
+    let attachment: Attachment = serde_json::from_value(attachment_json.clone()).unwrap();
+    assert_eq!(attachment.mime_type.as_deref(), Some("image/png"));
+    assert_eq!(attachment.thumbnails.as_ref().unwrap().small.as_ref().unwrap().width, 36);
+    assert_eq!(serde_json::to_value(&attachment).unwrap(), attachment_json);
+
+    // Uploads only send the URL and file name
+    let upload = Attachment::from_url("https://example.com/a.pdf", Some("a.pdf"));
+    assert_eq!(
+        serde_json::to_value(&upload).unwrap(),
+        json!({ "url": "https://example.com/a.pdf", "filename": "a.pdf" })
+    );
+
+    let collaborator_json = json!({ "id": "usrXXX", "email": "a@example.com", "name": "Ada", "permissionLevel": "edit" });
+    let collaborator: Collaborator = serde_json::from_value(collaborator_json.clone()).unwrap();
+    assert_eq!(collaborator.permission_level.as_deref(), Some("edit"));
+    assert_eq!(serde_json::to_value(&collaborator).unwrap(), collaborator_json);
+
+    let barcode: Barcode = serde_json::from_value(json!({ "text": "123", "type": "upce" })).unwrap();
+    assert_eq!(barcode.barcode_type.as_deref(), Some("upce"));
+
+    let button: Button = serde_json::from_value(json!({ "label": "Open", "url": "https://example.com" })).unwrap();
+    assert_eq!(button.label, "Open");
+
+    let ai_json = json!({ "state": "generated", "isStale": false, "value": "Summary" });
+    let ai: AiText = serde_json::from_value(ai_json.clone()).unwrap();
+    assert_eq!(ai.state, AiTextState::Generated);
+    assert_eq!(serde_json::to_value(&ai).unwrap(), ai_json);
+
+    let (rating, duration, currency): (Rating, Duration, Currency) =
+        serde_json::from_value(json!([4, 5400, 12.5])).unwrap();
+    assert_eq!(rating, Rating(4));
+    assert_eq!(duration.to_std(), Some(std::time::Duration::from_secs(5400)));
+    assert_eq!(Duration(f64::INFINITY).to_std(), None);
+    assert_eq!(Duration(-1.0).to_std(), None);
+    assert_eq!(currency, Currency(12.5));
+    assert_eq!(serde_json::to_value(rating).unwrap(), json!(4));
+}
+
+#[tokio::test]
+async fn list_bases_follows_offsets() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/meta/bases"))
+        .and(query_param("offset", "itr2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "bases": [{ "id": "app2", "name": "CRM", "permissionLevel": "read" }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/meta/bases"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "bases": [{ "id": "app1", "name": "Tasks", "permissionLevel": "create" }],
+            "offset": "itr2"
+        })))
+        .mount(&server)
+        .await;
+
+    let bases = mock_client(&server).list_bases().await.unwrap();
+    let ids: Vec<&str> = bases.iter().map(|b| b.id.as_str()).collect();
+    assert_eq!(ids, vec!["app1", "app2"]);
+    assert_eq!(bases[0].permission_level, PermissionLevel::Create);
+    assert_eq!(bases[1].permission_level, PermissionLevel::Read);
+}
+
+#[tokio::test]
+async fn get_base_schema_reads_typed_fields() {
+    let server = MockServer::start().await;
+    let schema_json = json!({
+        "tables": [{
+            "id": "tbl1",
+            "name": "Tasks",
+            "primaryFieldId": "fld1",
+            "description": "Things to do",
+            "fields": [
+                { "id": "fld1", "name": "Name", "type": "singleLineText" },
+                { "id": "fld2", "name": "Status", "type": "singleSelect", "options": {
+                    "choices": [{ "id": "sel1", "name": "Todo", "color": "blueLight2" }]
+                } },
+                { "id": "fld3", "name": "Estimate", "type": "number", "options": { "precision": 1 } },
+                { "id": "fld4", "name": "Project", "type": "multipleRecordLinks", "options": {
+                    "linkedTableId": "tbl2", "inverseLinkFieldId": "fld9", "prefersSingleRecordLink": false
+                } },
+                { "id": "fld5", "name": "Late", "type": "formula", "options": {
+                    "formula": "{Estimate} > 3", "isValid": true, "referencedFieldIds": ["fld3"],
+                    "result": { "type": "number", "options": { "precision": 0 } }
+                } },
+                { "id": "fld6", "name": "Total", "type": "rollup", "options": { "fieldIdInLinkedTable": "fld8" } }
+            ],
+            "views": [{ "id": "viw1", "name": "Grid view", "type": "grid" }]
+        }]
+    });
+
+    Mock::given(method("GET"))
+        .and(path(format!("/meta/bases/{}/tables", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(schema_json.clone()))
+        .mount(&server)
+        .await;
+
+    let schema = mock_client(&server).get_base_schema().await.unwrap();
+    let table = schema.table("Tasks").unwrap();
+    assert_eq!(table.primary_field().unwrap().name, "Name");
+    assert_eq!(table.views[0].view_type, "grid");
+
+    let FieldConfig::SingleSelect(ref select) = table.field("Status").unwrap().config else {
+        panic!("expected a single select");
+    };
+    assert_eq!(select.choices[0].color.as_deref(), Some("blueLight2"));
+    assert_eq!(table.field("fld3").unwrap().config, FieldConfig::Number(NumberOptions { precision: 1 }));
+    assert!(table.field("Late").unwrap().config.is_computed());
+    let rollup = &table.field("Total").unwrap().config;
+    assert!(rollup.is_computed() && rollup.type_name() == "rollup");
+
+    // Unknown types and their options survive a round trip
+    assert_eq!(serde_json::to_value(&schema).unwrap(), schema_json);
+}
+
+#[tokio::test]
+async fn create_table_sends_fields_with_primary_first() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/meta/bases/{}/tables", BASE_ID)))
+        .and(body_json(json!({
+            "name": "Tasks",
+            "description": "Things to do",
+            "fields": [
+                { "name": "Name", "type": "singleLineText" },
+                { "name": "Status", "type": "singleSelect", "options": {
+                    "choices": [{ "name": "Todo", "color": "blueLight2" }, { "name": "Done" }]
+                } }
+            ]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tblNew",
+            "name": "Tasks",
+            "description": "Things to do",
+            "primaryFieldId": "fld1",
+            "fields": [
+                { "id": "fld1", "name": "Name", "type": "singleLineText" },
+                { "id": "fld2", "name": "Status", "type": "singleSelect", "options": {
+                    "choices": [
+                        { "id": "sel1", "name": "Todo", "color": "blueLight2" },
+                        { "id": "sel2", "name": "Done", "color": "greenLight2" }
+                    ]
+                } }
+            ],
+            "views": [{ "id": "viw1", "name": "Grid view", "type": "grid" }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut definition = TableDefinition::new("Tasks", FieldDefinition::new("Name", FieldConfig::SingleLineText))
+        .with_field(FieldDefinition::new(
+            "Status",
+            FieldConfig::SingleSelect(SelectOptions {
+                choices: vec![SelectChoice::new("Todo", Some("blueLight2")), SelectChoice::new("Done", None)],
+            }),
+        ));
+    definition.description = Some("Things to do".to_string());
+
+    let table = mock_client(&server).create_table(&definition).await.unwrap();
+    assert_eq!(table.id, "tblNew");
+    assert_eq!(table.primary_field().unwrap().name, "Name");
+}
+
+#[tokio::test]
+async fn create_table_rejects_invalid_definitions() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server);
+
+    let formula = FieldDefinition::new(
+        "Late",
+        FieldConfig::Formula(FormulaOptions {
+            formula: "1".to_string(),
+            is_valid: None,
+            referenced_field_ids: None,
+            result: None,
+        }),
+    );
+    let with_formula = TableDefinition::new("Tasks", FieldDefinition::new("Name", FieldConfig::SingleLineText))
+        .with_field(formula);
+    let duplicate = TableDefinition::new("Tasks", FieldDefinition::new("Name", FieldConfig::SingleLineText))
+        .with_field(FieldDefinition::new("name", FieldConfig::Email));
+
+    for definition in [with_formula, duplicate] {
+        let err = client.create_table(&definition).await.unwrap_err();
+        assert!(matches!(err, AirtableError::InvalidParams(_)));
+    }
+}
+
+#[tokio::test]
+async fn update_table_patches_name_and_description() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path(format!("/meta/bases/{}/tables/tbl1", BASE_ID)))
+        .and(body_json(json!({ "name": "Todo" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tbl1",
+            "name": "Todo",
+            "primaryFieldId": "fld1",
+            "fields": [{ "id": "fld1", "name": "Name", "type": "singleLineText" }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let params = UpdateTableParams {
+        name: Some("Todo".to_string()),
+        ..Default::default()
+    };
+    let table = client.update_table("tbl1", params).await.unwrap();
+    assert_eq!(table.name, "Todo");
+    assert!(table.views.is_empty());
+
+    let err = client.update_table("tbl1", UpdateTableParams::new()).await.unwrap_err();
+    assert!(matches!(err, AirtableError::InvalidParams(_)));
+}
+
+#[tokio::test]
+async fn create_field_sends_typed_options() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(format!("/meta/bases/{}/tables/tbl1/fields", BASE_ID)))
+        .and(body_json(json!({
+            "name": "Due",
+            "description": "Deadline",
+            "type": "date",
+            "options": { "dateFormat": { "name": "iso" } }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "fldDue",
+            "name": "Due",
+            "description": "Deadline",
+            "type": "date",
+            "options": { "dateFormat": { "name": "iso", "format": "YYYY-MM-DD" } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("/meta/bases/{}/tables/tbl1/fields", BASE_ID)))
+        .and(body_json(json!({
+            "name": "Project",
+            "type": "multipleRecordLinks",
+            "options": { "linkedTableId": "tbl2" }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "fldProject",
+            "name": "Project",
+            "type": "multipleRecordLinks",
+            "options": { "linkedTableId": "tbl2", "inverseLinkFieldId": "fldBack", "isReversed": false }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+
+    let mut due = FieldDefinition::new(
+        "Due",
+        FieldConfig::Date(DateOptions {
+            date_format: DateFormat { name: "iso".to_string(), format: None },
+        }),
+    );
+    due.description = Some("Deadline".to_string());
+    let field = client.create_field("tbl1", &due).await.unwrap();
+    let FieldConfig::Date(ref options) = field.config else {
+        panic!("expected a date field");
+    };
+    assert_eq!(options.date_format.format.as_deref(), Some("YYYY-MM-DD"));
+
+    let link = FieldDefinition::new(
+        "Project",
+        FieldConfig::MultipleRecordLinks(LinkOptions {
+            linked_table_id: "tbl2".to_string(),
+            inverse_link_field_id: None,
+            view_id_for_record_selection: None,
+            is_reversed: None,
+            prefers_single_record_link: None,
+        }),
+    );
+    let field = client.create_field("tbl1", &link).await.unwrap();
+    assert!(matches!(
+        field.config,
+        FieldConfig::MultipleRecordLinks(ref o) if o.inverse_link_field_id.as_deref() == Some("fldBack")
+    ));
+
+    // Formulas are computed by Airtable and never sent
+    let formula = FieldDefinition::new(
+        "Late",
+        FieldConfig::Formula(FormulaOptions {
+            formula: "{Due} < TODAY()".to_string(),
+            is_valid: None,
+            referenced_field_ids: None,
+            result: None,
+        }),
+    );
+    let err = client.create_field("tbl1", &formula).await.unwrap_err();
+    assert!(matches!(err, AirtableError::InvalidParams(_)));
+}
+
+#[tokio::test]
+async fn update_field_patches_name_and_description() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("PATCH"))
+        .and(path(format!("/meta/bases/{}/tables/tbl1/fields/fld2", BASE_ID)))
+        .and(body_json(json!({ "description": "Hours" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "fld2",
+            "name": "Estimate",
+            "description": "Hours",
+            "type": "number",
+            "options": { "precision": 1 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = UpdateFieldParams {
+        description: Some("Hours".to_string()),
+        ..Default::default()
+    };
+    let field = mock_client(&server).update_field("tbl1", "fld2", params).await.unwrap();
+    assert_eq!(field.description.as_deref(), Some("Hours"));
+    assert_eq!(field.config, FieldConfig::Number(NumberOptions { precision: 1 }));
+}
+
+#[tokio::test]
+async fn create_base_returns_id_and_schema() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/meta/bases"))
+        .and(body_json(json!({
+            "name": "Customer A",
+            "workspaceId": "wspTeam",
+            "tables": [{ "name": "Tasks", "fields": [{ "name": "Name", "type": "singleLineText" }] }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "appNew",
+            "tables": [{
+                "id": "tbl1",
+                "name": "Tasks",
+                "primaryFieldId": "fld1",
+                "fields": [{ "id": "fld1", "name": "Name", "type": "singleLineText" }],
+                "views": [{ "id": "viw1", "name": "Grid view", "type": "grid" }]
+            }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Templates can be kept as JSON
+    let tables: Vec<TableDefinition> = serde_json::from_value(json!([
+        { "name": "Tasks", "fields": [{ "name": "Name", "type": "singleLineText" }] }
+    ]))
+    .unwrap();
+
+    let client = mock_client(&server);
+    let base = client.create_base("wspTeam", "Customer A", &tables).await.unwrap();
+    assert_eq!(base.id, "appNew");
+    assert_eq!(base.schema.table("Tasks").unwrap().primary_field_id, "fld1");
+
+    let err = client.create_base("wspTeam", "Empty", &[]).await.unwrap_err();
+    assert!(matches!(err, AirtableError::InvalidParams(_)));
+}
+
+// Live schema used by the schema-as-code tests
+#[cfg(feature = "declarative")]
+fn live_tasks_schema() -> Value {
+    json!({
+        "tables": [{
+            "id": "tbl1",
+            "name": "Tasks",
+            "primaryFieldId": "fld1",
+            "fields": [
+                { "id": "fld1", "name": "Name", "type": "singleLineText" },
+                { "id": "fld2", "name": "Status", "type": "singleSelect", "options": {
+                    "choices": [{ "id": "sel1", "name": "Todo", "color": "blueLight2" }]
+                } },
+                { "id": "fld3", "name": "Estimate", "type": "number", "options": { "precision": 1 } }
+            ]
+        }]
+    })
+}
+
+#[cfg(feature = "declarative")]
+const TASKS_SCHEMA_TOML: &str = r#"
+[[tables]]
+name = "Tasks"
+description = "Things to do"
+
+[[tables.fields]]
+name = "Name"
+type = "singleLineText"
+
+[[tables.fields]]
+name = "Status"
+type = "singleSelect"
+options = { choices = [{ name = "Todo" }, { name = "Done" }] }
+
+[[tables.fields]]
+name = "Estimate"
+type = "currency"
+options = { precision = 2, symbol = "$" }
+
+[[tables.fields]]
+name = "Due"
+type = "date"
+description = "Deadline"
+options = { dateFormat = { name = "iso" } }
+
+[[tables]]
+name = "Projects"
+
+[[tables.fields]]
+name = "Title"
+type = "singleLineText"
+
+[[tables.fields]]
+name = "Count"
+type = "count"
+"#;
+
+#[cfg(feature = "declarative")]
+#[test]
+fn schema_plan_lists_supported_and_unsupported_changes() {
+    let declared = DeclaredSchema::from_toml(TASKS_SCHEMA_TOML).unwrap();
+    let live = serde_json::from_value(live_tasks_schema()).unwrap();
+
+    let plan = declared.plan(&live).unwrap();
+    let printed: Vec<String> = plan.changes.iter().map(|c| c.to_string()).collect();
+    assert_eq!(
+        printed,
+        vec![
+            "~ table Tasks: description (none) -> \"Things to do\"",
+            "+ field Tasks.Due (date)",
+            "+ table Projects (fields: Title)",
+        ]
+    );
+
+    let unsupported: Vec<(&str, Option<&str>)> = plan
+        .unsupported
+        .iter()
+        .map(|u| (u.table.as_str(), u.field.as_deref()))
+        .collect();
+    assert_eq!(
+        unsupported,
+        vec![("Tasks", Some("Status")), ("Tasks", Some("Estimate")), ("Projects", Some("Count"))]
+    );
+    assert!(plan.unsupported[0].reason.contains("add: [Done]"));
+    assert!(plan.unsupported[1].reason.contains("number -> currency"));
+
+    // The same schema in YAML and JSON
+    let yaml = DeclaredSchema::from_yaml(&serde_yaml_ng::to_string(&declared).unwrap()).unwrap();
+    let json = DeclaredSchema::from_json(&serde_json::to_string(&declared).unwrap()).unwrap();
+    assert_eq!(yaml, declared);
+    assert_eq!(json, declared);
+
+    // Applying it changes nothing more
+    let matching = DeclaredSchema::from_json(&live_tasks_schema().to_string()).unwrap();
+    let plan = matching.plan(&live).unwrap();
+    assert!(plan.is_empty() && plan.unsupported.is_empty());
+}
+
+#[cfg(feature = "declarative")]
+#[test]
+fn schema_plan_matches_names_ignoring_case() {
+    let declared = DeclaredSchema::from_json(
+        &json!({
+            "tables": [{
+                "name": "TASKS",
+                "fields": [
+                    { "name": "name", "type": "singleLineText", "description": "What to do" },
+                    { "name": "Estimate", "type": "number", "options": { "precision": 1 } }
+                ]
+            }]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let live = serde_json::from_value(live_tasks_schema()).unwrap();
+
+    // Case-only differences are renames, not new tables or fields
+    let plan = declared.plan(&live).unwrap();
+    let printed: Vec<String> = plan.changes.iter().map(|c| c.to_string()).collect();
+    assert_eq!(
+        printed,
+        vec![
+            "~ table Tasks: name \"Tasks\" -> \"TASKS\"",
+            "~ field Tasks.Name: name \"Name\" -> \"name\", description (none) -> \"What to do\"",
+        ]
+    );
+    assert!(plan.unsupported.is_empty());
+    assert!(matches!(
+        plan.changes[0],
+        SchemaChange::UpdateTable { ref params, .. }
+            if params.name.as_deref() == Some("TASKS") && params.description.is_none()
+    ));
+}
+
+#[cfg(feature = "declarative")]
+#[test]
+fn schema_plan_rejects_duplicate_names() {
+    let live = serde_json::from_value(live_tasks_schema()).unwrap();
+
+    let tables = DeclaredSchema::from_json(
+        &json!({
+            "tables": [
+                { "name": "Projects", "fields": [{ "name": "Title", "type": "singleLineText" }] },
+                { "name": "projects", "fields": [{ "name": "Title", "type": "singleLineText" }] }
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let err = tables.plan(&live).unwrap_err();
+    assert!(matches!(err, AirtableError::Schema(ref msg) if msg.contains("two tables")), "{:?}", err);
+
+    // Both entries would rename the same live field
+    let fields = DeclaredSchema::from_json(
+        &json!({
+            "tables": [{
+                "name": "Tasks",
+                "fields": [
+                    { "name": "name", "type": "singleLineText" },
+                    { "name": "NAME", "type": "singleLineText" }
+                ]
+            }]
+        })
+        .to_string(),
+    )
+    .unwrap();
+    let err = fields.plan(&live).unwrap_err();
+    assert!(matches!(err, AirtableError::Schema(ref msg) if msg.contains("two fields")), "{:?}", err);
+}
+
+#[cfg(feature = "declarative")]
+#[tokio::test]
+async fn apply_schema_plan_makes_supported_changes() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(format!("/meta/bases/{}/tables", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(live_tasks_schema()))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(format!("/meta/bases/{}/tables/tbl1", BASE_ID)))
+        .and(body_json(json!({ "description": "Things to do" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&live_tasks_schema()["tables"][0]))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("/meta/bases/{}/tables/tbl1/fields", BASE_ID)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "fldDue", "name": "Due", "type": "date", "options": { "dateFormat": { "name": "iso" } }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("/meta/bases/{}/tables", BASE_ID)))
+        .and(body_json(json!({
+            "name": "Projects",
+            "fields": [{ "name": "Title", "type": "singleLineText" }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "tbl2", "name": "Projects", "primaryFieldId": "fldT",
+            "fields": [{ "id": "fldT", "name": "Title", "type": "singleLineText" }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = mock_client(&server);
+    let declared = DeclaredSchema::from_toml(TASKS_SCHEMA_TOML).unwrap();
+    let plan = client.plan_schema(&declared).await.unwrap();
+    assert!(matches!(plan.changes[1], SchemaChange::CreateField { ref table_id, .. } if table_id == "tbl1"));
+
+    let applied = client.apply_schema_plan(&plan).await.unwrap();
+    assert_eq!(applied, 3);
+}